@@ -0,0 +1,371 @@
+//! Cache of check results between runs
+//!
+//! Results of rules are reused for notes which are not changed since the last run.
+//! Results of rules which [depend on vault](obsidian_tidy_core::rule::Rule::depends_on_vault)
+//! are reused only if no note in vault is changed, added or removed
+
+use crate::linter::NoteCheck;
+use obsidian_tidy_config::Config;
+use obsidian_tidy_core::{
+    directories::directories,
+    rule::{Rule, Violation},
+};
+use obsidian_tidy_rules::{CUSTOM_RULES_DIR, lua, wasm};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    hash::Hasher,
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tracing::{debug, instrument, warn};
+
+/// Version of cache format. Cache with another version is dropped
+const VERSION: u32 = 2;
+
+/// Folder of cache files in [`data_local_dir`](obsidian_tidy_core::directories::Directories::data_local_dir)
+const CACHE_DIR: &str = "cache";
+
+/// FNV-1a hasher. Unlike [`DefaultHasher`](std::hash::DefaultHasher),
+/// its output doesn't change between Rust releases, so saved hashes stay valid
+#[derive(Debug, Clone, Copy)]
+struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Hash of config with configs of subfolders, custom rules of vault in `root`
+/// and version of obsidian-tidy. Results of rules depend on all of them
+fn config_hash(config: &Config, root: &Path) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.write(&serde_json::to_vec(config).unwrap_or_default());
+
+    for (dir, config) in config.overrides() {
+        hasher.write(dir.as_os_str().as_encoded_bytes());
+        hasher.write(&serde_json::to_vec(config).unwrap_or_default());
+    }
+
+    let dir = root.join(CUSTOM_RULES_DIR);
+    let scripts = lua::scripts(&dir).unwrap_or_default();
+    let plugins = wasm::plugins(&dir).unwrap_or_default();
+
+    for script in scripts.iter().chain(&plugins) {
+        hasher.write(script.as_os_str().as_encoded_bytes());
+        hasher.write(&fs::read(script).unwrap_or_default());
+    }
+
+    hasher.finish()
+}
+
+/// State of note file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    modified: Option<SystemTime>,
+    size: u64,
+    hash: u64,
+}
+
+impl Fingerprint {
+    /// Get fingerprint of file.
+    /// Content is hashed only if modification time or size differ from `previous`
+    #[instrument(skip(previous), err)]
+    pub fn new(path: &Path, previous: Option<&Self>) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata.modified().ok();
+        let size = metadata.len();
+
+        if let Some(previous) = previous
+            && modified.is_some()
+            && previous.modified == modified
+            && previous.size == size
+        {
+            return Ok(*previous);
+        }
+
+        Ok(Self {
+            modified,
+            size,
+            hash: hash(&fs::read(path)?),
+        })
+    }
+
+    /// Files have the same content. Modification time is ignored
+    #[must_use]
+    pub const fn same_content(&self, other: &Self) -> bool {
+        self.size == other.size && self.hash == other.hash
+    }
+}
+
+/// Results of rules for one note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedNote {
+    fingerprint: Fingerprint,
+
+    /// Violations by rule name. Rules which failed are not saved
+    violations: BTreeMap<String, Vec<Violation>>,
+}
+
+impl CachedNote {
+    #[must_use]
+    pub fn new(fingerprint: Fingerprint, config: &Config, check: &NoteCheck) -> Self {
+        let mut violations: BTreeMap<_, _> = config
+            .rules()
+            .iter()
+            .filter(|rule| {
+                !check
+                    .failures
+                    .iter()
+                    .any(|failure| failure.rule == rule.name())
+            })
+            .map(|rule| (rule.name().to_string(), Vec::new()))
+            .collect();
+
+        // Suppressed violations are saved too, so unused suppressions are found in the next run
+        for violation in check.violations.iter().chain(&check.suppressed) {
+            if let Some(rule) = violations.get_mut(&violation.rule) {
+                rule.push(violation.violation.clone());
+            }
+        }
+
+        Self {
+            fingerprint,
+            violations,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn fingerprint(&self) -> &Fingerprint {
+        &self.fingerprint
+    }
+
+    /// Saved violations of rule
+    #[must_use]
+    pub fn violations(&self, rule: &str) -> Option<&[Violation]> {
+        self.violations.get(rule).map(Vec::as_slice)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cache {
+    version: u32,
+    config: u64,
+
+    /// Notes by path relative to vault
+    notes: HashMap<PathBuf, CachedNote>,
+}
+
+impl Cache {
+    /// Empty cache for config of vault in `root`
+    #[must_use]
+    pub fn new(config: &Config, root: &Path) -> Self {
+        Self {
+            version: VERSION,
+            config: config_hash(config, root),
+            notes: HashMap::new(),
+        }
+    }
+
+    /// Path to cache file of vault in [`CACHE_DIR`]
+    #[must_use]
+    pub fn path(root: &Path) -> PathBuf {
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let name = format!("{:016x}.json", hash(root.as_os_str().as_encoded_bytes()));
+
+        directories().data_local_dir().join(CACHE_DIR).join(name)
+    }
+
+    /// Load cache from file.
+    /// Returns empty cache if file doesn't exist, is broken or was saved for another config
+    #[instrument(skip(config))]
+    pub fn load(path: &Path, config: &Config, root: &Path) -> Self {
+        let empty = Self::new(config, root);
+
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!("Failed read cache: {error}");
+                }
+
+                return empty;
+            }
+        };
+
+        match serde_json::from_slice::<Self>(&bytes) {
+            Ok(cache) if (cache.version, cache.config) == (empty.version, empty.config) => cache,
+            Ok(_) => {
+                debug!("Cache is saved for another config or version");
+                empty
+            }
+            Err(error) => {
+                warn!("Broken cache: {error}");
+                empty
+            }
+        }
+    }
+
+    #[instrument(skip(self), err)]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&CachedNote> {
+        self.notes.get(path)
+    }
+
+    /// Get note only if its content isn't changed
+    #[must_use]
+    pub fn get_fresh(&self, path: &Path, fingerprint: &Fingerprint) -> Option<&CachedNote> {
+        self.get(path)
+            .filter(|note| note.fingerprint.same_content(fingerprint))
+    }
+
+    pub fn insert(&mut self, path: PathBuf, note: CachedNote) {
+        self.notes.insert(path, note);
+    }
+
+    /// Any note is changed, added or removed since cache was saved
+    #[must_use]
+    pub fn is_vault_changed(&self, fingerprints: &HashMap<PathBuf, Fingerprint>) -> bool {
+        fingerprints.len() != self.notes.len()
+            || fingerprints
+                .iter()
+                .any(|(path, fingerprint)| self.get_fresh(path, fingerprint).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_config::builder::ConfigBuilder;
+    use tempfile::TempDir;
+
+    fn fingerprint(hash: u64) -> Fingerprint {
+        Fingerprint {
+            modified: None,
+            size: 10,
+            hash,
+        }
+    }
+
+    fn cached_note(hash: u64) -> CachedNote {
+        CachedNote {
+            fingerprint: fingerprint(hash),
+            violations: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn fingerprint_of_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+
+        fs::write(&path, "first").unwrap();
+        let first = Fingerprint::new(&path, None).unwrap();
+
+        fs::write(&path, "other").unwrap();
+        let second = Fingerprint::new(&path, None).unwrap();
+
+        assert!(!first.same_content(&second));
+        assert!(second.same_content(&Fingerprint::new(&path, Some(&second)).unwrap()));
+    }
+
+    #[test]
+    fn vault_changed() {
+        let mut cache = Cache::new(&ConfigBuilder::default().build(), Path::new(""));
+        cache.insert(PathBuf::from("a.md"), cached_note(1));
+
+        let same = HashMap::from([(PathBuf::from("a.md"), fingerprint(1))]);
+        let changed = HashMap::from([(PathBuf::from("a.md"), fingerprint(2))]);
+        let added = HashMap::from([
+            (PathBuf::from("a.md"), fingerprint(1)),
+            (PathBuf::from("b.md"), fingerprint(3)),
+        ]);
+
+        assert!(!cache.is_vault_changed(&same));
+        assert!(cache.is_vault_changed(&changed));
+        assert!(cache.is_vault_changed(&added));
+        assert!(cache.is_vault_changed(&HashMap::new()));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cache").join("vault.json");
+        let config = ConfigBuilder::default().build();
+
+        let mut cache = Cache::new(&config, dir.path());
+        cache.insert(PathBuf::from("a.md"), cached_note(1));
+        cache.save(&path).unwrap();
+
+        assert_eq!(Cache::load(&path, &config, dir.path()), cache);
+    }
+
+    #[test]
+    fn load_missing() {
+        let dir = TempDir::new().unwrap();
+        let config = ConfigBuilder::default().build();
+
+        assert_eq!(
+            Cache::load(&dir.path().join("cache.json"), &config, dir.path()),
+            Cache::new(&config, dir.path())
+        );
+    }
+
+    #[test]
+    fn custom_rules_changed() {
+        let dir = TempDir::new().unwrap();
+        let config = ConfigBuilder::default().build();
+        let rules = dir.path().join(CUSTOM_RULES_DIR);
+        fs::create_dir_all(&rules).unwrap();
+
+        fs::write(rules.join("rule.lua"), "function check(note) end").unwrap();
+        let first = config_hash(&config, dir.path());
+
+        fs::write(
+            rules.join("rule.lua"),
+            "function check(note) return nil end",
+        )
+        .unwrap();
+        assert_ne!(config_hash(&config, dir.path()), first);
+    }
+}
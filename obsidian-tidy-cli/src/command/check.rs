@@ -1,58 +1,91 @@
 //! Run check to vault
 
-use super::Cli;
+use super::{Cli, load_config, load_content};
+use crate::cache::{Cache, CachedNote, Fingerprint};
 use crate::command::runner::Runner;
+use crate::diagnostic::{
+    Emitter, OutputFormat, Report,
+    emitter::{HumanEmitter, JsonEmitter, JsonLinesEmitter, SarifEmitter},
+};
+use crate::exit_status::ExitStatus;
+use crate::linter::Linter;
 use obsidian_parser::prelude::Note as _;
-use obsidian_tidy_config::{Config, Error as ConfigError, loader::ConfigLoader};
-use obsidian_tidy_core::rule::{Content, Violation};
-use obsidian_tidy_rules::ALL_RULES;
-use rayon::prelude::*;
-use std::{
-    fs::OpenOptions,
-    ops::Range,
-    path::{Path, PathBuf},
+use obsidian_tidy_config::{Config, Error as ConfigError};
+use obsidian_tidy_core::{
+    discovery::Error as DiscoveryError,
+    rule::{Rule, Severity},
 };
+use rayon::prelude::*;
+use std::{collections::HashMap, io::IsTerminal, path::Path};
 use thiserror::Error;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Config load error: {0}")]
     Config(#[from] ConfigError),
+
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Failed search notes: {0}")]
+    Discovery(#[from] DiscoveryError),
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct RunnerCheck;
+pub struct RunnerCheck {
+    max_violations: Option<usize>,
+    fail_on: Severity,
+    format: OutputFormat,
+    no_cache: bool,
+}
 
 impl RunnerCheck {
-    pub const fn new() -> Self {
-        Self
+    /// `max_violations` - count of violations which are allowed before check fails.
+    /// Only violations with severity `fail_on` or higher are counted.
+    /// `no_cache` - run all rules on all notes and don't save results
+    pub const fn new(
+        max_violations: Option<usize>,
+        fail_on: Severity,
+        format: OutputFormat,
+        no_cache: bool,
+    ) -> Self {
+        Self {
+            max_violations,
+            fail_on,
+            format,
+            no_cache,
+        }
     }
-}
 
-#[instrument(skip(path))]
-fn load_config(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
-    let mut file = OpenOptions::new().read(true).open(path)?;
-
-    ConfigLoader::default()
-        .available_rules(&ALL_RULES)
-        .load(&mut file)
-}
+    fn emitter(&self, config: &Config, root: &Path, color: bool) -> Box<dyn Emitter> {
+        match self.format {
+            OutputFormat::Human => Box::new(HumanEmitter::new(color)),
+            OutputFormat::Json => Box::new(JsonEmitter),
+            OutputFormat::JsonLines => Box::new(JsonLinesEmitter),
+            OutputFormat::Sarif => Box::new(SarifEmitter::new(
+                root,
+                config
+                    .rules()
+                    .iter()
+                    .map(|rule| (rule.name().to_string(), rule.description().to_string())),
+            )),
+        }
+    }
 
-#[derive(Debug)]
-#[allow(unused)]
-pub struct Diagnostic {
-    message: String,
-    location: Range<usize>,
-    path: PathBuf,
-}
+    /// Get exit status for result of check
+    const fn exit_status(&self, count_violations: usize, count_failures: usize) -> ExitStatus {
+        let max_violations = match self.max_violations {
+            Some(max_violations) => max_violations,
+            None => 0,
+        };
 
-impl Diagnostic {
-    fn from_violation(violation: &Violation, path: impl Into<PathBuf>) -> Self {
-        Self {
-            message: violation.message().to_string(),
-            location: violation.location().clone(),
-            path: path.into(),
+        if count_failures > 0 {
+            ExitStatus::RuleFailed
+        } else if count_violations > max_violations {
+            ExitStatus::Violations
+        } else {
+            ExitStatus::Success
         }
     }
 }
@@ -61,46 +94,123 @@ impl Runner for RunnerCheck {
     type Error = self::Error;
 
     #[instrument]
-    #[allow(unused)]
-    fn run(&self, args: &Cli) -> Result<(), Self::Error> {
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
         debug!("Run command `check`");
 
         let config = load_config(args.config())?;
-        let content = Content::new(&args.path);
+        let cache_path = (!self.no_cache).then(|| Cache::path(&args.path));
+        let previous = cache_path.as_deref().map_or_else(
+            || Cache::new(&config, &args.path),
+            |path| Cache::load(path, &config, &args.path),
+        );
 
-        let notes_to_check = content.vault.notes();
-        let diagnostics: Vec<Diagnostic> = notes_to_check
+        let content = load_content(args, &config)?;
+        let linter = Linter::new(&config, &args.path);
+
+        let fingerprints: HashMap<_, _> = content
+            .vault
+            .notes()
             .par_iter()
-            .flat_map(|note| {
-                config
-                    .rules()
-                    .iter()
-                    .filter_map(|rule| match rule.check(&content, note) {
-                        Ok(violations) => Some(violations),
-                        Err(e) => {
-                            eprintln!("Rule '{}' failed: {}", rule.name(), e);
-                            None
-                        }
-                    })
-                    .flatten()
-                    .map(|violation| Diagnostic::from_violation(&violation, note.path().unwrap()))
-                    .collect::<Vec<_>>()
+            .filter_map(|note| {
+                let path = linter.relative_path(&note.path()?);
+                let previous = previous.get(&path).map(CachedNote::fingerprint);
+                let fingerprint = Fingerprint::new(&args.path.join(&path), previous).ok()?;
+
+                Some((path, fingerprint))
             })
             .collect();
 
-        println!("{:?}", diagnostics);
+        let vault_changed = previous.is_vault_changed(&fingerprints);
+        debug!("Vault changed since last run: {vault_changed}");
 
-        /*
-        for violation in violations {
-            println!(
-                "{} in `{}`: {}",
-                "PROBLEM".red().bold(),
-                violation.from().strip_prefix(&args.path).unwrap().display(),
-                violation.message().yellow()
-            );
+        let checks: Vec<_> = content
+            .vault
+            .notes()
+            .par_iter()
+            .filter_map(|note| {
+                let path = note.path()?.into_owned();
+                let relative_path = linter.relative_path(&path);
+                let fingerprint = fingerprints.get(&relative_path).copied();
+                let cached = fingerprint
+                    .and_then(|fingerprint| previous.get_fresh(&relative_path, &fingerprint));
+
+                let check = linter.check_with(&content, note, |rule| {
+                    if vault_changed && rule.depends_on_vault() {
+                        return None;
+                    }
+
+                    cached?.violations(rule.name()).map(<[_]>::to_vec)
+                });
+
+                let cached = fingerprint.map(|fingerprint| {
+                    CachedNote::new(fingerprint, config.for_note(&relative_path), &check)
+                });
+
+                let report = (!check.violations.is_empty())
+                    .then(|| check.report(&relative_path, Linter::read_source(note)));
+
+                Some((path, check.failures, report, cached))
+            })
+            .collect();
+
+        let mut report = Report::new();
+        let mut cache = Cache::new(&config, &args.path);
+        let mut count_failures = 0;
+
+        for (path, failures, note_report, cached) in checks {
+            for failure in &failures {
+                eprintln!(
+                    "Rule '{}' failed on `{}`: {}",
+                    failure.rule,
+                    path.display(),
+                    failure.error
+                );
+            }
+
+            count_failures += failures.len();
+
+            if let Some(cached) = cached {
+                cache.insert(linter.relative_path(&path), cached);
+            }
+
+            if let Some(note_report) = note_report {
+                report.insert(linter.relative_path(&path), note_report);
+            }
         }
-        */
 
-        Ok(())
+        if let Some(cache_path) = cache_path
+            && let Err(error) = cache.save(&cache_path)
+        {
+            warn!("Failed save cache: {error}");
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        self.emitter(&config, &args.path, stdout.is_terminal())
+            .emit(&report, &mut stdout)?;
+
+        Ok(self.exit_status(report.count_at_least(self.fail_on), count_failures))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_status() {
+        let runner = RunnerCheck::new(None, Severity::Warning, OutputFormat::Human, false);
+
+        assert_eq!(runner.exit_status(0, 0), ExitStatus::Success);
+        assert_eq!(runner.exit_status(1, 0), ExitStatus::Violations);
+        assert_eq!(runner.exit_status(1, 1), ExitStatus::RuleFailed);
+    }
+
+    #[test]
+    fn exit_status_with_max_violations() {
+        let runner = RunnerCheck::new(Some(10), Severity::Warning, OutputFormat::Human, false);
+
+        assert_eq!(runner.exit_status(10, 0), ExitStatus::Success);
+        assert_eq!(runner.exit_status(11, 0), ExitStatus::Violations);
+        assert_eq!(runner.exit_status(0, 1), ExitStatus::RuleFailed);
     }
 }
@@ -1,7 +1,7 @@
 //! Generate shell completions
 
 use super::runner::Runner;
-use crate::Cli;
+use crate::{Cli, ExitStatus};
 use clap::CommandFactory;
 use std::{convert::Infallible, io};
 use tracing::{debug, instrument};
@@ -21,7 +21,7 @@ impl Runner for RunnerCompletions {
     type Error = Infallible;
 
     #[instrument]
-    fn run(&self, args: &Cli) -> Result<(), Self::Error> {
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
         debug!("Run completions command");
 
         clap_complete::generate(
@@ -31,6 +31,6 @@ impl Runner for RunnerCompletions {
             &mut io::stdout(),
         );
 
-        Ok(())
+        Ok(ExitStatus::Success)
     }
 }
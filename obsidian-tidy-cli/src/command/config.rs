@@ -0,0 +1,236 @@
+//! Commands for config file
+
+use super::{Cli, available_rules};
+use crate::command::runner::Runner;
+use crate::diagnostic::{
+    Diagnostic, Emitter, LineIndex, NoteReport, OutputFormat, Report,
+    emitter::{HumanEmitter, JsonEmitter, JsonLinesEmitter, SarifEmitter},
+};
+use crate::exit_status::ExitStatus;
+use crate::linter::RuleViolation;
+use clap::Subcommand;
+use obsidian_tidy_config::{
+    Error as ConfigError,
+    schema::schema,
+    template::Template,
+    update::Updater,
+    validate::{FileProblems, Validator},
+};
+use obsidian_tidy_core::rule::{Category, Severity, Violation};
+use owo_colors::OwoColorize;
+use std::{
+    fs,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+/// Name of pseudo rule for problems of config
+pub const INVALID_CONFIG: &str = "invalid-config";
+
+#[derive(Debug, Clone, Copy, Subcommand)]
+pub enum ConfigCommand {
+    /// Report all problems of config files of vault
+    Validate {
+        /// Format of output
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+    },
+
+    /// Add new rules to config and remove rules which don't exist anymore.
+    /// Comments and formatting of config are preserved
+    Update {
+        /// Template for state of added rules
+        #[arg(long, value_enum, default_value_t = Template::Standard)]
+        template: Template,
+    },
+
+    /// Print JSON Schema of config for editors
+    Schema,
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Config problem: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunnerConfigValidate {
+    format: OutputFormat,
+}
+
+impl RunnerConfigValidate {
+    pub const fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    fn emitter(self, root: &Path, color: bool) -> Box<dyn Emitter> {
+        match self.format {
+            OutputFormat::Human => Box::new(HumanEmitter::new(color)),
+            OutputFormat::Json => Box::new(JsonEmitter),
+            OutputFormat::JsonLines => Box::new(JsonLinesEmitter),
+            OutputFormat::Sarif => Box::new(SarifEmitter::new(
+                root,
+                [(
+                    INVALID_CONFIG.to_string(),
+                    "Problem of config file".to_string(),
+                )],
+            )),
+        }
+    }
+}
+
+/// Report of config file. `path` is relative to vault, if config is in vault
+fn note_report(root: &Path, file: &FileProblems) -> (PathBuf, NoteReport) {
+    let path = file.path.strip_prefix(root).unwrap_or(&file.path);
+    let index = LineIndex::new(&file.source);
+
+    let diagnostics = file
+        .problems
+        .iter()
+        .filter_map(|problem| {
+            let violation = Violation::new(&problem.message, problem.location.clone()).ok()?;
+
+            let violation = RuleViolation {
+                rule: INVALID_CONFIG.to_string(),
+                category: Category::Other,
+                severity: problem.severity,
+                violation,
+            };
+
+            Some(Diagnostic::new(path, &violation, &index))
+        })
+        .collect();
+
+    (
+        path.to_path_buf(),
+        NoteReport::new(file.source.clone(), diagnostics),
+    )
+}
+
+impl Runner for RunnerConfigValidate {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `config validate`");
+
+        let files = Validator::default()
+            .available_rules(&available_rules(&args.path)?)
+            .validate_vault(&args.config())?;
+
+        let report: Report = files
+            .iter()
+            .map(|file| note_report(&args.path, file))
+            .collect();
+
+        let mut stdout = std::io::stdout().lock();
+        self.emitter(&args.path, stdout.is_terminal())
+            .emit(&report, &mut stdout)?;
+
+        if report.count_at_least(Severity::Error) > 0 {
+            Ok(ExitStatus::ConfigError)
+        } else {
+            Ok(ExitStatus::Success)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunnerConfigUpdate {
+    template: Template,
+}
+
+impl RunnerConfigUpdate {
+    pub const fn new(template: Template) -> Self {
+        Self { template }
+    }
+}
+
+impl Runner for RunnerConfigUpdate {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `config update`");
+
+        let config_path = args.config();
+        let source = fs::read_to_string(&config_path)?;
+
+        let update = Updater::new(self.template)
+            .available_rules(&available_rules(&args.path)?)
+            .update(&source, &config_path)?;
+
+        if update.is_empty() {
+            println!("{}", "✓ Config is up to date".green().bold());
+            return Ok(ExitStatus::Success);
+        }
+
+        fs::write(&config_path, &update.source)?;
+
+        for rule in &update.added {
+            println!("{} Added rule `{}`", "+".green().bold(), rule.bold());
+        }
+
+        for rule in &update.removed {
+            println!(
+                "{} Removed rule `{}` which doesn't exist anymore",
+                "-".red().bold(),
+                rule.bold()
+            );
+        }
+
+        Ok(ExitStatus::Success)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunnerConfigSchema;
+
+impl Runner for RunnerConfigSchema {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `config schema`");
+
+        let schema = schema(&available_rules(&args.path)?);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        Ok(ExitStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_config::validate::Problem;
+
+    #[test]
+    fn report_positions() {
+        let file = FileProblems {
+            path: PathBuf::from("vault/.obsidian-tidy.toml"),
+            source: "[rules.content.empty-contnet]\nenable = true\n".to_string(),
+            problems: vec![Problem {
+                severity: Severity::Error,
+                message: "Unknown rule `empty-contnet`".to_string(),
+                location: 15..28,
+            }],
+        };
+
+        let (path, report) = note_report(Path::new("vault"), &file);
+        let diagnostic = &report.diagnostics()[0];
+
+        assert_eq!(path, Path::new(".obsidian-tidy.toml"));
+        assert_eq!(diagnostic.rule(), INVALID_CONFIG);
+        assert_eq!(diagnostic.start().to_string(), "1:16");
+        assert_eq!(diagnostic.end().to_string(), "1:29");
+    }
+}
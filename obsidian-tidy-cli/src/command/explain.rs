@@ -0,0 +1,131 @@
+//! Explain rule with its documentation
+
+use super::{Cli, available_rules};
+use crate::command::runner::Runner;
+use crate::exit_status::ExitStatus;
+use obsidian_tidy_config::{Error as ConfigError, validate::suggest};
+use obsidian_tidy_core::rule::Rule;
+use std::fmt::Write as _;
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Unknown rule `{rule}`{hint}")]
+    UnknownRule { rule: String, hint: String },
+
+    #[error("Config problem: {0}")]
+    Config(#[from] ConfigError),
+}
+
+/// Reference of rule in markdown. `level` is level of heading with name of rule
+pub(super) fn markdown<R>(rule: &R, level: usize) -> String
+where
+    R: Rule + ?Sized,
+{
+    let heading = "#".repeat(level);
+    let subheading = "#".repeat(level + 1);
+    let docs = rule.docs();
+
+    let fixable = rule.fixable().map_or_else(
+        || "no".to_string(),
+        |applicability| format!("`{applicability}` fixes"),
+    );
+
+    let mut text = format!(
+        "{heading} `{}`\n\n{}\n\n- Category: `{}`\n- Default severity: `{}`\n- Fixable: {fixable}\n",
+        rule.name(),
+        rule.description(),
+        rule.category().as_str(),
+        rule.default_severity(),
+    );
+
+    if !docs.rationale().is_empty() {
+        let _ = write!(text, "\n{subheading} Why\n\n{}\n", docs.rationale());
+    }
+
+    let options = rule.default_options();
+
+    if !options.is_empty() {
+        let _ = write!(
+            text,
+            "\n{subheading} Options\n\n| Option | Default | Description |\n| --- | --- | --- |\n"
+        );
+
+        for (name, default) in &options {
+            let description = docs.options().get(name).map_or("", String::as_str);
+            let _ = writeln!(text, "| `{name}` | `{default}` | {description} |");
+        }
+    }
+
+    for (title, example) in [("Bad", docs.bad_example()), ("Good", docs.good_example())] {
+        if let Some(example) = example {
+            let _ = write!(
+                text,
+                "\n{subheading} {title}\n\n```markdown\n{}\n```\n",
+                example.trim_end()
+            );
+        }
+    }
+
+    text
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunnerExplain {
+    rule: String,
+}
+
+impl RunnerExplain {
+    pub const fn new(rule: String) -> Self {
+        Self { rule }
+    }
+}
+
+impl Runner for RunnerExplain {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `explain`");
+
+        let rules = available_rules(&args.path)?;
+
+        let Some(rule) = rules.iter().find(|rule| rule.name() == self.rule) else {
+            let hint = suggest(&self.rule, rules.iter().map(Rule::name))
+                .map(|rule| format!(", did you mean `{rule}`?"))
+                .unwrap_or_default();
+
+            return Err(Error::UnknownRule {
+                rule: self.rule.clone(),
+                hint,
+            });
+        };
+
+        print!("{}", markdown(rule, 1));
+        Ok(ExitStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_rules::ALL_RULES;
+
+    #[test]
+    fn markdown_of_rule() {
+        let rule = ALL_RULES
+            .iter()
+            .find(|rule| rule.name() == "empty-content")
+            .unwrap();
+
+        let text = markdown(rule, 2);
+
+        assert!(text.starts_with("## `empty-content`\n"));
+        assert!(text.contains("- Category: `content`\n"));
+        assert!(text.contains("- Fixable: no\n"));
+        assert!(text.contains("### Why\n"));
+        assert!(text.contains("| `min-words` | `1` | Note with fewer words is empty |\n"));
+        assert!(text.contains("### Bad\n\n```markdown\n---\ntags: [idea]\n---\n```\n"));
+    }
+}
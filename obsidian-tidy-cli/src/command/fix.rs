@@ -0,0 +1,426 @@
+//! Apply fixes of violations to vault
+
+use super::{Cli, load_config, load_content};
+use crate::command::runner::Runner;
+use crate::exit_status::ExitStatus;
+use crate::linter::{Linter, RuleFailure};
+use obsidian_parser::prelude::Note as _;
+use obsidian_tidy_config::Error as ConfigError;
+use obsidian_tidy_core::{
+    Note, NoteError,
+    discovery::Error as DiscoveryError,
+    rule::{
+        Applicability, Content,
+        content::{Error as ContentError, parse_note},
+        fix::apply_fixes,
+    },
+};
+use rayon::prelude::*;
+use similar::TextDiff;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+
+/// Limit of re-checks for one note.
+/// Protects from rules whose fixes undo each other
+const MAX_ITERATIONS: usize = 10;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Config load error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("IO error: {0}")]
+    IO(#[from] io::Error),
+
+    #[error("Failed search notes: {0}")]
+    Discovery(#[from] DiscoveryError),
+
+    #[error("Failed read note: {0}")]
+    Source(#[from] ContentError),
+
+    #[error("Failed parse fixed note: {0}")]
+    Parser(#[from] NoteError),
+}
+
+/// Result of fixing one note
+#[derive(Debug, Clone, Default)]
+pub struct NoteFix {
+    pub original: String,
+    pub fixed: String,
+
+    /// Count of applied fixes
+    pub applied: usize,
+
+    /// Failures of rules on original note
+    pub failures: Vec<RuleFailure>,
+}
+
+impl NoteFix {
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.original != self.fixed
+    }
+}
+
+/// Counts of fixed problems, changed notes and failures
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Summary {
+    fixes: usize,
+    notes: usize,
+    failures: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunnerFix {
+    unsafe_fixes: bool,
+    diff: bool,
+}
+
+impl RunnerFix {
+    /// `unsafe_fixes` - apply fixes which may change meaning of note.
+    /// `diff` - print unified diff instead of writing notes
+    pub const fn new(unsafe_fixes: bool, diff: bool) -> Self {
+        Self { unsafe_fixes, diff }
+    }
+
+    /// The most dangerous fixes which are allowed
+    const fn applicability(&self) -> Applicability {
+        if self.unsafe_fixes {
+            Applicability::Unsafe
+        } else {
+            Applicability::Safe
+        }
+    }
+
+    /// Get exit status for result of fix.
+    /// In diff mode notes which would be changed are counted as violations
+    const fn exit_status(&self, count_changed: usize, count_failures: usize) -> ExitStatus {
+        if count_failures > 0 {
+            ExitStatus::RuleFailed
+        } else if self.diff && count_changed > 0 {
+            ExitStatus::Violations
+        } else {
+            ExitStatus::Success
+        }
+    }
+
+    /// Apply fixes to source of note and check it again until nothing changes
+    #[instrument(skip_all)]
+    pub fn fix_note(
+        &self,
+        linter: Linter,
+        content: &Content,
+        note: &Note,
+        path: &Path,
+    ) -> Result<NoteFix, Error> {
+        let original = content.source(note)?.into_owned();
+        let mut result = NoteFix {
+            fixed: original.clone(),
+            original,
+            ..NoteFix::default()
+        };
+
+        let mut current = note.clone();
+
+        for iteration in 0..MAX_ITERATIONS {
+            let content = content.clone().with_source(path, result.fixed.as_str());
+            let check = linter.check(&content, &current);
+
+            if iteration == 0 {
+                result.failures = check.failures;
+            }
+
+            let allowed = check
+                .violations
+                .iter()
+                .filter_map(|violation| violation.violation.fix())
+                .filter(|fix| fix.applicability() <= self.applicability());
+
+            let (fixed, applied) = apply_fixes(&result.fixed, allowed);
+
+            if applied == 0 || fixed == result.fixed {
+                break;
+            }
+
+            if iteration + 1 == MAX_ITERATIONS {
+                warn!("Fixes for `{}` are not stable", path.display());
+            }
+
+            current = parse_note(&fixed, path)?;
+            result.fixed = fixed;
+            result.applied += applied;
+        }
+
+        Ok(result)
+    }
+
+    /// Write fixed notes or print their diff. One broken note doesn't stop fixing others
+    fn apply(&self, linter: Linter, fixes: &[(PathBuf, Result<NoteFix, Error>)]) -> Summary {
+        let mut summary = Summary::default();
+
+        for (path, fix) in fixes {
+            let fix = match fix {
+                Ok(fix) => fix,
+                Err(error) => {
+                    eprintln!("Failed fix `{}`: {error}", path.display());
+                    summary.failures += 1;
+                    continue;
+                }
+            };
+
+            for failure in &fix.failures {
+                eprintln!(
+                    "Rule '{}' failed on `{}`: {}",
+                    failure.rule,
+                    path.display(),
+                    failure.error
+                );
+            }
+
+            summary.failures += fix.failures.len();
+
+            if !fix.is_changed() {
+                continue;
+            }
+
+            let relative_path = linter.relative_path(path);
+
+            if self.diff {
+                print!("{}", unified_diff(&relative_path, fix));
+            } else {
+                if let Err(error) = write_atomic(path, &fix.fixed) {
+                    eprintln!("Failed write `{}`: {error}", relative_path.display());
+                    summary.failures += 1;
+                    continue;
+                }
+
+                println!(
+                    "Fixed {} in `{}`",
+                    plural(fix.applied, "problem"),
+                    relative_path.display()
+                );
+            }
+
+            summary.fixes += fix.applied;
+            summary.notes += 1;
+        }
+
+        summary
+    }
+}
+
+/// Unified diff between original and fixed note. `path` is relative to the vault
+fn unified_diff(path: &Path, fix: &NoteFix) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+
+    TextDiff::from_lines(&fix.original, &fix.fixed)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+/// Replace file by writing to temporary file in the same directory and renaming it.
+/// Renaming ignores permissions of file, so read-only file isn't replaced
+#[instrument(skip(contents), err)]
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let permissions = fs::metadata(path)?.permissions();
+
+    if permissions.readonly() {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "file is read-only",
+        ));
+    }
+
+    let mut file = NamedTempFile::new_in(dir)?;
+
+    file.write_all(contents.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.as_file().set_permissions(permissions)?;
+    file.persist(path).map_err(|error| error.error)?;
+
+    Ok(())
+}
+
+impl Runner for RunnerFix {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `fix`");
+
+        let config = load_config(args.config())?;
+        let content = load_content(args, &config)?;
+        let linter = Linter::new(&config, &args.path);
+
+        let mut fixes: Vec<(PathBuf, Result<NoteFix, Error>)> = content
+            .vault
+            .notes()
+            .par_iter()
+            .filter_map(|note| {
+                let path = note.path()?.into_owned();
+                let fix = self.fix_note(linter, &content, note, &path);
+                Some((path, fix))
+            })
+            .collect();
+
+        fixes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let Summary {
+            fixes: count_fixes,
+            notes: count_notes,
+            failures: count_failures,
+        } = self.apply(linter, &fixes);
+
+        // In diff mode stdout contains only diff, so it can be piped to `patch`
+        if self.diff {
+            if count_notes > 0 {
+                eprintln!(
+                    "{} in {} would be fixed",
+                    plural(count_fixes, "problem"),
+                    plural(count_notes, "note")
+                );
+            }
+        } else if count_notes == 0 {
+            println!("✓ Nothing to fix");
+        } else {
+            println!(
+                "✓ Fixed {} in {}",
+                plural(count_fixes, "problem"),
+                plural(count_notes, "note")
+            );
+        }
+
+        Ok(self.exit_status(count_notes, count_failures))
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{count} {word}")
+    } else {
+        format!("{count} {word}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::config;
+    use tempfile::TempDir;
+
+    #[test]
+    fn fix_until_stable() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, "a    b  c").unwrap();
+
+        let config = config();
+        let linter = Linter::new(&config, dir.path());
+        let content = Content::new(dir.path());
+        let note = parse_note("a    b  c", &path).unwrap();
+
+        let fix = RunnerFix::new(false, false)
+            .fix_note(linter, &content, &note, &path)
+            .unwrap();
+
+        assert_eq!(fix.fixed, "a b c");
+        assert_eq!(fix.applied, 4);
+        assert!(fix.is_changed());
+    }
+
+    #[test]
+    fn read_only_note() {
+        let dir = TempDir::new().unwrap();
+        let read_only = dir.path().join("a.md");
+        let writable = dir.path().join("b.md");
+        fs::write(&read_only, "a  b").unwrap();
+        fs::write(&writable, "a  b").unwrap();
+
+        let mut permissions = fs::metadata(&read_only).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&read_only, permissions).unwrap();
+
+        let config = config();
+        let fix = NoteFix {
+            original: "a  b".to_string(),
+            fixed: "a b".to_string(),
+            applied: 1,
+            ..NoteFix::default()
+        };
+        let fixes = [
+            (read_only.clone(), Ok(fix.clone())),
+            (writable.clone(), Ok(fix)),
+        ];
+
+        let summary = RunnerFix::new(false, false).apply(Linter::new(&config, dir.path()), &fixes);
+
+        assert_eq!(
+            summary,
+            Summary {
+                fixes: 1,
+                notes: 1,
+                failures: 1
+            }
+        );
+        assert_eq!(fs::read_to_string(&read_only).unwrap(), "a  b");
+        assert_eq!(fs::read_to_string(&writable).unwrap(), "a b");
+    }
+
+    #[test]
+    fn diff() {
+        let fix = NoteFix {
+            original: "# Title\n\nfirst  line\nsecond\n".to_string(),
+            fixed: "# Title\n\nfirst line\nsecond\n".to_string(),
+            ..NoteFix::default()
+        };
+
+        let diff = unified_diff(Path::new("notes/note.md"), &fix);
+
+        assert_eq!(
+            diff,
+            "--- a/notes/note.md\n\
+             +++ b/notes/note.md\n\
+             @@ -1,4 +1,4 @@\n \
+             # Title\n \n\
+             -first  line\n\
+             +first line\n \
+             second\n"
+        );
+    }
+
+    #[test]
+    fn exit_status() {
+        let runner = RunnerFix::new(false, false);
+        assert_eq!(runner.exit_status(1, 0), ExitStatus::Success);
+        assert_eq!(runner.exit_status(1, 1), ExitStatus::RuleFailed);
+
+        let runner = RunnerFix::new(false, true);
+        assert_eq!(runner.exit_status(0, 0), ExitStatus::Success);
+        assert_eq!(runner.exit_status(1, 0), ExitStatus::Violations);
+    }
+
+    #[test]
+    fn write() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("note.md");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::Cli;
 use crate::command::runner::Runner;
+use crate::exit_status::ExitStatus;
 use obsidian_tidy_config::ConfigSaver;
 use obsidian_tidy_config::template::Template;
 use obsidian_tidy_config::{builder::ConfigBuilder, error::Error as ConfigError};
@@ -41,7 +42,7 @@ impl Runner for RunnerInit {
     type Error = self::Error;
 
     #[instrument]
-    fn run(&self, args: &Cli) -> Result<(), Self::Error> {
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
         debug!("Run command `init`");
 
         let config_path = args.config();
@@ -65,6 +66,6 @@ impl Runner for RunnerInit {
             .path(&config_path)
             .save(&mut file)?;
 
-        Ok(())
+        Ok(ExitStatus::Success)
     }
 }
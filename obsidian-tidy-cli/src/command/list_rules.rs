@@ -1,31 +1,127 @@
 //! Get list all rules
 
-use super::Cli;
+use super::{Cli, explain::markdown};
 use crate::command::runner::Runner;
+use crate::exit_status::ExitStatus;
+use clap::ValueEnum;
 use itertools::Itertools;
 use obsidian_tidy_config::template::Template;
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Docs, Options, Rule, Severity, SharedErrorRule, ToggleableRule,
+};
 use owo_colors::OwoColorize;
-use std::convert::Infallible;
+use serde::Serialize;
+use thiserror::Error;
 use tracing::{debug, instrument};
 
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Format of list of rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum RulesFormat {
+    /// Short list for terminal
+    #[default]
+    Human,
+
+    /// Array of rules with documentation in JSON
+    Json,
+
+    /// Reference page of rules in markdown
+    Markdown,
+}
+
+/// Rule with documentation for JSON output
+#[derive(Debug, Serialize)]
+struct RuleReference<'a> {
+    name: &'a str,
+    description: &'a str,
+    category: Category,
+    enabled: bool,
+    default_severity: Severity,
+    fixable: Option<Applicability>,
+    options: Options,
+    docs: Docs,
+}
+
+impl<'a> From<&'a ToggleableRule<SharedErrorRule>> for RuleReference<'a> {
+    fn from(rule: &'a ToggleableRule<SharedErrorRule>) -> Self {
+        Self {
+            name: rule.name(),
+            description: rule.description(),
+            category: rule.category(),
+            enabled: rule.is_enabled(),
+            default_severity: rule.default_severity(),
+            fixable: rule.fixable(),
+            options: rule.default_options(),
+            docs: rule.docs(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct RunnerListRules {
     from_template: Template,
+    format: RulesFormat,
 }
 
 impl RunnerListRules {
-    pub const fn new(from_template: Template) -> Self {
-        Self { from_template }
+    pub const fn new(from_template: Template, format: RulesFormat) -> Self {
+        Self {
+            from_template,
+            format,
+        }
+    }
+
+    fn json(&self) -> Result<(), Error> {
+        let rules: Vec<RuleReference> = self.from_template.iter().map(Into::into).collect();
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+
+        Ok(())
+    }
+
+    fn markdown(&self) {
+        println!("# Rules");
+
+        let rules_by_category = self
+            .from_template
+            .iter()
+            .sorted_by_key(|rule| rule.category())
+            .chunk_by(|rule| rule.category());
+
+        for (category, rules) in &rules_by_category {
+            println!("\n## {category}");
+
+            for rule in rules {
+                print!("\n{}", markdown(rule, 3));
+            }
+        }
     }
 }
 
 impl Runner for RunnerListRules {
-    type Error = Infallible;
+    type Error = self::Error;
 
     #[instrument]
-    fn run(&self, args: &Cli) -> Result<(), Self::Error> {
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
         debug!("Run command `list-rules`");
 
+        match self.format {
+            RulesFormat::Human => {}
+            RulesFormat::Json => {
+                self.json()?;
+                return Ok(ExitStatus::Success);
+            }
+            RulesFormat::Markdown => {
+                self.markdown();
+                return Ok(ExitStatus::Success);
+            }
+        }
+
         let rules_by_category = self
             .from_template
             .iter()
@@ -72,6 +168,6 @@ impl Runner for RunnerListRules {
             total_rules.to_string().bold().yellow()
         );
 
-        Ok(())
+        Ok(ExitStatus::Success)
     }
 }
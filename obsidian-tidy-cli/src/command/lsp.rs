@@ -0,0 +1,104 @@
+//! Run language server over stdio
+
+use super::{Cli, load_config};
+use crate::command::runner::Runner;
+use crate::exit_status::ExitStatus;
+use crate::lsp::{self, Server};
+use lsp_server::{Connection, ProtocolError};
+use lsp_types::{InitializeParams, MessageType};
+use obsidian_tidy_config::builder::ConfigBuilder;
+use obsidian_tidy_core::discovery::Error as DiscoveryError;
+use std::path::PathBuf;
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    #[error("Failed search notes: {0}")]
+    Discovery(#[from] DiscoveryError),
+
+    #[error("Language server error: {0}")]
+    Server(#[from] lsp::Error),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunnerLsp;
+
+impl RunnerLsp {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Path of the first workspace folder opened in editor
+#[allow(deprecated)]
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())
+        .and_then(|uri| uri.to_file_path().ok())
+}
+
+impl Runner for RunnerLsp {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `lsp`");
+
+        let (connection, io_threads) = Connection::stdio();
+
+        let params = connection.initialize(serde_json::to_value(Server::capabilities())?)?;
+        let params: InitializeParams = serde_json::from_value(params)?;
+        let root = workspace_root(&params).unwrap_or_else(|| args.path.clone());
+
+        let config = load_config(root.join(crate::CONFIG_FILE)).unwrap_or_else(|error| {
+            warn!("Failed load config: {error}");
+
+            // Editor is waiting for server, so only tell about problem
+            let message = format!("obsidian-tidy: failed load config: {error}");
+            let _ = connection
+                .sender
+                .send(lsp::show_message(MessageType::ERROR, message));
+
+            ConfigBuilder::default().build()
+        });
+
+        let mut server = Server::new(&root, config, args.no_ignore).or_else(|error| {
+            warn!("Failed search notes: {error}");
+
+            let message = format!("obsidian-tidy: invalid exclude pattern: {error}");
+            let _ = connection
+                .sender
+                .send(lsp::show_message(MessageType::ERROR, message));
+
+            Server::new(&root, ConfigBuilder::default().build(), args.no_ignore)
+        })?;
+
+        if let Some(request) = lsp::register_watchers(&params.capabilities) {
+            connection
+                .sender
+                .send(request)
+                .map_err(|_| lsp::Error::Disconnected)?;
+        }
+
+        server.run(&connection)?;
+
+        drop(connection);
+        io_threads.join()?;
+
+        Ok(ExitStatus::Success)
+    }
+}
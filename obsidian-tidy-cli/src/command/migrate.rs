@@ -0,0 +1,98 @@
+//! Command for migrate settings of other linters to config
+
+use super::Cli;
+use crate::command::runner::Runner;
+use crate::exit_status::ExitStatus;
+use clap::ValueEnum;
+use obsidian_tidy_config::{
+    ConfigSaver,
+    error::Error as ConfigError,
+    migrate::{self, JS_LINTER_SETTINGS},
+};
+use owo_colors::OwoColorize;
+use std::{fs, path::PathBuf};
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Config problem: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("Settings of linter not found: {}", .0.display())]
+    NotFound(PathBuf),
+
+    #[error("Config file already exists")]
+    AlreadyExists(PathBuf),
+}
+
+/// Linter which settings are migrated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum MigrateFrom {
+    /// obsidian-linter plugin for Obsidian
+    JsLinter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunnerMigrate {
+    from: MigrateFrom,
+    override_config: bool,
+}
+
+impl RunnerMigrate {
+    pub const fn new(from: MigrateFrom, override_config: bool) -> Self {
+        Self {
+            from,
+            override_config,
+        }
+    }
+}
+
+impl Runner for RunnerMigrate {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `migrate`");
+
+        let config_path = args.config();
+
+        if config_path.is_file() && !self.override_config {
+            return Err(Error::AlreadyExists(config_path));
+        }
+
+        let migration = match self.from {
+            MigrateFrom::JsLinter => {
+                let settings = args.path.join(JS_LINTER_SETTINGS);
+
+                if !settings.is_file() {
+                    return Err(Error::NotFound(settings));
+                }
+
+                migrate::from_js_linter(&fs::read_to_string(&settings)?)?
+            }
+        };
+
+        let mut file = fs::File::create(&config_path)?;
+
+        ConfigSaver::new(&migration.config)
+            .path(&config_path)
+            .save(&mut file)?;
+
+        println!(
+            "{} Written {}",
+            "✓".green().bold(),
+            config_path.display().bold()
+        );
+
+        for setting in &migration.unmapped {
+            println!("{} {setting}", "!".yellow().bold());
+        }
+
+        Ok(ExitStatus::Success)
+    }
+}
@@ -1,23 +1,81 @@
 mod check;
 mod completions;
+mod config;
+mod explain;
+mod fix;
 mod init;
 mod list_rules;
+mod lsp;
+mod migrate;
 mod runner;
+mod watch;
 
-use super::Cli;
+use super::{Cli, ExitStatus};
 use crate::command::{
-    check::RunnerCheck, completions::RunnerCompletions, init::RunnerInit,
-    list_rules::RunnerListRules, runner::SharedRunner,
+    check::RunnerCheck,
+    completions::RunnerCompletions,
+    config::{RunnerConfigSchema, RunnerConfigUpdate, RunnerConfigValidate},
+    explain::RunnerExplain,
+    fix::RunnerFix,
+    init::RunnerInit,
+    list_rules::{RulesFormat, RunnerListRules},
+    lsp::RunnerLsp,
+    migrate::{MigrateFrom, RunnerMigrate},
+    runner::SharedRunner,
+    watch::RunnerWatch,
 };
+use crate::diagnostic::OutputFormat;
 use clap::Subcommand;
-use obsidian_tidy_config::template::Template;
-use std::sync::Arc;
+use config::ConfigCommand;
+use obsidian_tidy_config::{
+    Config, Error as ConfigError, loader::ConfigLoader, template::Template,
+};
+use obsidian_tidy_core::{
+    discovery::{Discovery, DiscoveryBuilder, Error as DiscoveryError},
+    rule::{Content, Severity, SharedErrorRule},
+};
+use std::{path::Path, sync::Arc};
 use tracing::{debug, instrument};
 
-#[derive(Debug, Clone, Copy, Subcommand)]
+#[derive(Debug, Clone, Subcommand)]
 pub enum Command {
     /// Run rules
-    Check,
+    Check {
+        /// Count of violations which are allowed before exit with failure
+        #[arg(long, value_name = "N")]
+        max_violations: Option<usize>,
+
+        /// Minimal severity of violations which fail check
+        /// [possible values: error, warning, info, hint]
+        #[arg(long, value_name = "SEVERITY", default_value_t = Severity::Warning)]
+        fail_on: Severity,
+
+        /// Format of output
+        #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+        format: OutputFormat,
+
+        /// Don't use results of previous runs
+        #[arg(long)]
+        no_cache: bool,
+    },
+
+    /// Apply fixes of violations and write notes
+    Fix {
+        /// Also apply fixes which may change meaning of notes
+        #[arg(long = "unsafe")]
+        unsafe_fixes: bool,
+
+        /// Print unified diff of fixes without writing notes.
+        /// Exit with failure if any note would change
+        #[arg(long)]
+        diff: bool,
+    },
+
+    /// Watch vault and check notes again when they change
+    Watch,
+
+    /// Run language server over stdio for editors
+    Lsp,
 
     /// Initialization of config for obsidian-tidy
     Init {
@@ -30,11 +88,38 @@ pub enum Command {
         template: Template,
     },
 
+    /// Write config from settings of other linter
+    Migrate {
+        /// Linter which settings are migrated
+        #[arg(long, value_enum)]
+        from: MigrateFrom,
+
+        /// Override config if already exists
+        #[arg(long = "override")]
+        override_config: bool,
+    },
+
+    /// Work with config files
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
     /// List all available built‑in rules
     ListRules {
         /// Get rules from template
         #[arg(long, value_enum, default_value_t = Template::All)]
         from_template: Template,
+
+        /// Format of output
+        #[arg(long, value_enum, default_value_t = RulesFormat::Human)]
+        format: RulesFormat,
+    },
+
+    /// Show documentation of rule: why it exists, options and examples
+    Explain {
+        /// Name of rule
+        rule: String,
     },
 
     /// Generate shell completions
@@ -45,24 +130,83 @@ pub enum Command {
     },
 }
 
+/// Built-in rules with custom rules of vault in `root`
+pub(crate) fn available_rules(root: &Path) -> Result<Vec<SharedErrorRule>, ConfigError> {
+    Ok(obsidian_tidy_rules::available_rules(root)?)
+}
+
+/// Load config of vault with user-global config, `extends` and configs of subfolders
+#[instrument(skip(path))]
+pub(crate) fn load_config(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+    let path = path.as_ref();
+    let root = path.parent().unwrap_or_else(|| Path::new(""));
+
+    ConfigLoader::default()
+        .available_rules(&available_rules(root)?)
+        .load_vault(path)
+}
+
+/// Search notes of vault in `root` by config
+pub(crate) fn discovery(
+    root: &Path,
+    config: &Config,
+    no_ignore: bool,
+) -> Result<Discovery, DiscoveryError> {
+    DiscoveryBuilder::new(root)
+        .exclude(&config.general().exclude)
+        .respect_gitignore(config.general().respect_gitignore)
+        .no_ignore(no_ignore)
+        .build()
+}
+
+/// Parse notes of vault which are not ignored
+pub(crate) fn load_content(args: &Cli, config: &Config) -> Result<Content, DiscoveryError> {
+    let notes = discovery(&args.path, config, args.no_ignore)?.notes();
+    Ok(Content::from_files(&args.path, &notes))
+}
+
 impl Command {
     /// Run command
     ///
     /// # Errors
     ///
     /// Returns an error about executing a specific command.
-    /// Just run unwrap and don't worry about it.
+    /// Use [`ExitStatus::from_error`] to get exit status for it.
     #[instrument(skip(args))]
-    pub fn execute(self, args: &Cli) -> Result<(), Arc<dyn std::error::Error + Send + Sync>> {
+    pub fn execute(
+        self,
+        args: &Cli,
+    ) -> Result<ExitStatus, Arc<dyn std::error::Error + Send + Sync>> {
         debug!("Execute command");
 
         let runner: SharedRunner = match self {
-            Command::Check => RunnerCheck::new().into(),
+            Command::Check {
+                max_violations,
+                fail_on,
+                format,
+                no_cache,
+            } => RunnerCheck::new(max_violations, fail_on, format, no_cache).into(),
+            Command::Fix { unsafe_fixes, diff } => RunnerFix::new(unsafe_fixes, diff).into(),
+            Command::Watch => RunnerWatch::new().into(),
+            Command::Lsp => RunnerLsp::new().into(),
             Command::Init {
                 override_config,
                 template,
             } => RunnerInit::new(override_config, template).into(),
-            Command::ListRules { from_template } => RunnerListRules::new(from_template).into(),
+            Command::Migrate {
+                from,
+                override_config,
+            } => RunnerMigrate::new(from, override_config).into(),
+            Command::Config { command } => match command {
+                ConfigCommand::Validate { format } => RunnerConfigValidate::new(format).into(),
+                ConfigCommand::Update { template } => RunnerConfigUpdate::new(template).into(),
+                ConfigCommand::Schema => RunnerConfigSchema.into(),
+            },
+            Command::ListRules {
+                from_template,
+                format,
+            } => RunnerListRules::new(from_template, format).into(),
+            Command::Explain { rule } => RunnerExplain::new(rule).into(),
             Command::Completions { shell } => RunnerCompletions::new(shell).into(),
         };
 
@@ -1,4 +1,4 @@
-use crate::Cli;
+use crate::{Cli, ExitStatus};
 use std::{ops::Deref, sync::Arc};
 
 pub type DynRunner<E> = Box<dyn Runner<Error = E>>;
@@ -6,7 +6,7 @@ pub type DynRunner<E> = Box<dyn Runner<Error = E>>;
 pub trait Runner {
     type Error: std::error::Error + Send + Sync;
 
-    fn run(&self, args: &Cli) -> Result<(), Self::Error>;
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error>;
 }
 
 impl<R> Runner for Box<R>
@@ -15,7 +15,7 @@ where
 {
     type Error = R::Error;
 
-    fn run(&self, args: &Cli) -> Result<(), Self::Error> {
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
         self.as_ref().run(args)
     }
 }
@@ -75,7 +75,7 @@ where
 {
     type Error = Arc<dyn std::error::Error + Send + Sync>;
 
-    fn run(&self, args: &Cli) -> Result<(), Self::Error> {
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
         self.inner
             .run(args)
             .map_err(|err| Arc::new(err) as Arc<dyn std::error::Error + Send + Sync>)
@@ -0,0 +1,436 @@
+//! Check notes again when they change
+
+use super::{Cli, discovery, load_config};
+use crate::command::runner::Runner;
+use crate::diagnostic::{Emitter, Report, emitter::HumanEmitter};
+use crate::exit_status::ExitStatus;
+use crate::linter::Linter;
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+use obsidian_parser::prelude::Note as _;
+use obsidian_tidy_config::{Config, Error as ConfigError};
+use obsidian_tidy_core::{
+    Note,
+    discovery::{Discovery, Error as DiscoveryError, IGNORE_FILE},
+    links,
+    rule::{Content, content::parse_note},
+};
+use rayon::prelude::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+
+/// Time to collect events after the first one.
+/// Editors write a file by several events
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Config load error: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Failed search notes: {0}")]
+    Discovery(#[from] DiscoveryError),
+
+    #[error("Failed watch vault: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+/// Notes of vault which are kept in memory and updated on changes
+#[derive(Debug)]
+pub struct WatchedVault {
+    discovery: Discovery,
+    notes: BTreeMap<PathBuf, Note>,
+    content: Content,
+}
+
+impl WatchedVault {
+    pub fn new(discovery: Discovery) -> Self {
+        let content = Content::from_files(discovery.root(), &discovery.notes());
+        let notes = content
+            .vault
+            .notes()
+            .iter()
+            .filter_map(|note| Some((note.path()?.into_owned(), note.clone())))
+            .collect();
+
+        Self {
+            discovery,
+            notes,
+            content,
+        }
+    }
+
+    #[must_use]
+    pub const fn content(&self) -> &Content {
+        &self.content
+    }
+
+    /// Paths of all notes
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.notes.keys()
+    }
+
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&Note> {
+        self.notes.get(path)
+    }
+
+    /// File is known note or new note which isn't ignored
+    #[must_use]
+    pub fn is_note(&self, path: &Path) -> bool {
+        self.notes.contains_key(path) || self.discovery.contains(path)
+    }
+
+    /// Parse changed notes again.
+    /// Returns paths of notes which must be checked: changed notes and notes which link to them
+    #[instrument(skip(self))]
+    pub fn update(&mut self, changed: &BTreeSet<PathBuf>) -> BTreeSet<PathBuf> {
+        for path in changed {
+            let note = match fs::read_to_string(path) {
+                Ok(source) => parse_note(&source, path)
+                    .inspect_err(|error| warn!("Failed parse `{}`: {error}", path.display()))
+                    .ok(),
+                Err(error) => {
+                    debug!("Note `{}` is removed: {error}", path.display());
+                    None
+                }
+            };
+
+            match note {
+                Some(note) => self.notes.insert(path.clone(), note),
+                None => self.notes.remove(path),
+            };
+        }
+
+        self.content = Content::from_notes(self.discovery.root(), self.notes.values().cloned());
+
+        let stems: Vec<_> = changed
+            .iter()
+            .filter_map(|path| path.file_stem())
+            .map(|stem| stem.to_string_lossy())
+            .collect();
+
+        self.notes
+            .iter()
+            .filter(|(path, note)| {
+                changed.contains(*path)
+                    || note
+                        .content()
+                        .is_ok_and(|text| stems.iter().any(|stem| links_to(&text, stem)))
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+/// Text has wikilink or markdown link to note with `stem`
+fn links_to(text: &str, stem: &str) -> bool {
+    links::find(text).iter().any(|link| {
+        let target = link.note().replace("%20", " ");
+        let name = target.rsplit('/').next().unwrap_or(&target);
+
+        name.strip_suffix(".md").unwrap_or(name) == stem
+    })
+}
+
+/// Changes of file may change which notes are ignored
+fn is_ignore_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == IGNORE_FILE || name == ".gitignore")
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunnerWatch;
+
+impl RunnerWatch {
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Load config and notes again. Ignore rules of notes may change with config
+    fn reload(
+        config_path: &Path,
+        root: &Path,
+        no_ignore: bool,
+    ) -> Result<(Config, WatchedVault), Error> {
+        let config = load_config(config_path)?;
+        let vault = WatchedVault::new(discovery(root, &config, no_ignore)?);
+
+        Ok((config, vault))
+    }
+
+    /// Parse changed notes again. Returns notes which must be checked.
+    /// Removed and ignored notes are forgotten in `dirty`, so they aren't reported when they come back
+    fn update(
+        vault: &mut WatchedVault,
+        changed: &BTreeSet<PathBuf>,
+        dirty: &mut BTreeSet<PathBuf>,
+    ) -> BTreeSet<PathBuf> {
+        let affected = vault.update(changed);
+        dirty.retain(|path| vault.get(path).is_some());
+
+        affected
+    }
+
+    /// Check notes and print diagnostics for them.
+    /// `dirty` - notes with problems, notes which become clean are reported
+    fn check(
+        config: &Config,
+        vault: &WatchedVault,
+        paths: &BTreeSet<PathBuf>,
+        dirty: &mut BTreeSet<PathBuf>,
+    ) {
+        let linter = Linter::new(config, vault.discovery.root());
+
+        let checks: Vec<_> = paths
+            .par_iter()
+            .filter_map(|path| {
+                let note = vault.get(path)?;
+                let check = linter.check(vault.content(), note);
+                let report = check.report(&linter.relative_path(path), Linter::read_source(note));
+
+                Some((path, check.failures, report))
+            })
+            .collect();
+
+        let mut report = Report::new();
+
+        for (path, failures, note_report) in checks {
+            for failure in &failures {
+                eprintln!(
+                    "Rule '{}' failed on `{}`: {}",
+                    failure.rule,
+                    path.display(),
+                    failure.error
+                );
+            }
+
+            if note_report.diagnostics().is_empty() {
+                if dirty.remove(path) {
+                    println!(
+                        "✓ `{}` has no problems now",
+                        linter.relative_path(path).display()
+                    );
+                }
+            } else {
+                dirty.insert(path.clone());
+            }
+
+            report.insert(linter.relative_path(path), note_report);
+        }
+
+        let mut stdout = std::io::stdout().lock();
+
+        if let Err(error) = HumanEmitter::new(stdout.is_terminal()).emit(&report, &mut stdout) {
+            warn!("Failed print diagnostics: {error}");
+        }
+    }
+}
+
+/// Collect changed paths from event of file system
+fn collect_paths(event: notify::Result<Event>, changed: &mut BTreeSet<PathBuf>) {
+    match event {
+        Ok(event) => {
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                changed.extend(event.paths);
+            }
+        }
+        Err(error) => warn!("Error while watching: {error}"),
+    }
+}
+
+impl Runner for RunnerWatch {
+    type Error = self::Error;
+
+    #[instrument]
+    fn run(&self, args: &Cli) -> Result<ExitStatus, Self::Error> {
+        debug!("Run command `watch`");
+
+        let root = fs::canonicalize(&args.path)?;
+        let config_path = fs::canonicalize(args.config()).unwrap_or_else(|_| args.config());
+
+        let mut config = load_config(&config_path)?;
+        let mut vault = WatchedVault::new(discovery(&root, &config, args.no_ignore)?);
+
+        let mut dirty = BTreeSet::new();
+        Self::check(
+            &config,
+            &vault,
+            &vault.paths().cloned().collect(),
+            &mut dirty,
+        );
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(&root, RecursiveMode::Recursive)?;
+
+        println!(
+            "Watching `{}` for changes. Press Ctrl+C to stop",
+            root.display()
+        );
+
+        while let Ok(event) = receiver.recv() {
+            let mut changed = BTreeSet::new();
+            collect_paths(event, &mut changed);
+
+            while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+                collect_paths(event, &mut changed);
+            }
+
+            // Configs of subfolders have the same name as config of vault
+            let count_changed = changed.len();
+            changed.retain(|path| path.file_name() != config_path.file_name());
+            let config_changed = changed.len() != count_changed;
+
+            if config_changed {
+                match Self::reload(&config_path, &root, args.no_ignore) {
+                    Ok((new_config, new_vault)) => {
+                        println!("Config reloaded");
+                        config = new_config;
+                        vault = new_vault;
+                    }
+                    Err(error) => eprintln!("Failed reload config: {error}"),
+                }
+            }
+
+            let ignore_changed = !config_changed && changed.iter().any(|path| is_ignore_file(path));
+
+            if ignore_changed {
+                match discovery(&root, &config, args.no_ignore) {
+                    Ok(discovery) => {
+                        println!("Ignore files reloaded");
+                        vault = WatchedVault::new(discovery);
+                    }
+                    Err(error) => eprintln!("Failed reload ignore files: {error}"),
+                }
+            }
+
+            changed.retain(|path| vault.is_note(path));
+
+            let mut affected = Self::update(&mut vault, &changed, &mut dirty);
+
+            if config_changed || ignore_changed {
+                affected = vault.paths().cloned().collect();
+            }
+
+            if !affected.is_empty() {
+                Self::check(&config, &vault, &affected, &mut dirty);
+            }
+        }
+
+        Ok(ExitStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::config;
+    use obsidian_tidy_core::discovery::DiscoveryBuilder;
+    use tempfile::TempDir;
+
+    #[test]
+    fn links() {
+        assert!(links_to("See [[note]]", "note"));
+        assert!(links_to("See [[folder/note|alias]]", "note"));
+        assert!(links_to("See [[note#Heading]]", "note"));
+        assert!(links_to("See [link](folder/note.md)", "note"));
+        assert!(links_to("See [link](<my%20note.md>)", "my note"));
+        assert!(!links_to("See [[notes]]", "note"));
+        assert!(!links_to("```\n[[note]]\n```", "note"));
+        assert!(!links_to("See note", "note"));
+    }
+
+    #[test]
+    fn update() {
+        let dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+
+        fs::write(root.join("a.md"), "First").unwrap();
+        fs::write(root.join("b.md"), "Link to [[a]]").unwrap();
+        fs::write(root.join("c.md"), "Unrelated").unwrap();
+
+        let mut vault = WatchedVault::new(DiscoveryBuilder::new(&root).build().unwrap());
+
+        fs::write(root.join("a.md"), "Changed").unwrap();
+        let affected = vault.update(&BTreeSet::from([root.join("a.md")]));
+
+        assert_eq!(
+            affected,
+            BTreeSet::from([root.join("a.md"), root.join("b.md")])
+        );
+
+        fs::remove_file(root.join("c.md")).unwrap();
+        let affected = vault.update(&BTreeSet::from([root.join("c.md")]));
+
+        assert!(affected.is_empty());
+        assert!(vault.get(&root.join("c.md")).is_none());
+    }
+
+    #[test]
+    fn forget_removed() {
+        let dir = TempDir::new().unwrap();
+        let root = fs::canonicalize(dir.path()).unwrap();
+        let path = root.join("a.md");
+        fs::write(&path, "a  b").unwrap();
+
+        let config = config();
+        let mut vault = WatchedVault::new(DiscoveryBuilder::new(&root).build().unwrap());
+        let mut dirty = BTreeSet::new();
+
+        RunnerWatch::check(&config, &vault, &BTreeSet::from([path.clone()]), &mut dirty);
+        assert_eq!(dirty, BTreeSet::from([path.clone()]));
+
+        fs::remove_file(&path).unwrap();
+        RunnerWatch::update(&mut vault, &BTreeSet::from([path.clone()]), &mut dirty);
+        assert!(dirty.is_empty());
+
+        fs::write(&path, "a b").unwrap();
+        let affected = RunnerWatch::update(&mut vault, &BTreeSet::from([path.clone()]), &mut dirty);
+        RunnerWatch::check(&config, &vault, &affected, &mut dirty);
+
+        assert!(dirty.is_empty());
+    }
+
+    #[test]
+    fn ignore_files() {
+        assert!(is_ignore_file(Path::new("/vault/.obtidyignore")));
+        assert!(is_ignore_file(Path::new("/vault/folder/.gitignore")));
+        assert!(!is_ignore_file(Path::new("/vault/note.md")));
+    }
+
+    #[test]
+    fn is_note() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+
+        for path in ["folder/note.md", ".obsidian/note.md", "templates/note.md"] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::write(root.join(path), "text").unwrap();
+        }
+
+        let discovery = DiscoveryBuilder::new(root)
+            .exclude(["templates/**"])
+            .build()
+            .unwrap();
+        let vault = WatchedVault::new(discovery);
+
+        assert!(vault.is_note(&root.join("folder/note.md")));
+        assert!(!vault.is_note(&root.join(".obsidian/note.md")));
+        assert!(!vault.is_note(&root.join("templates/note.md")));
+        assert!(!vault.is_note(Path::new("/other/note.md")));
+    }
+}
@@ -0,0 +1,344 @@
+//! Human-readable output with source snippets
+
+use super::Emitter;
+use crate::diagnostic::{Diagnostic, LineIndex, Report};
+use itertools::Itertools;
+use obsidian_tidy_core::rule::Severity;
+use owo_colors::{OwoColorize, Style};
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Max count of source lines shown for one diagnostic
+const MAX_SNIPPET_LINES: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HumanEmitter {
+    color: bool,
+}
+
+impl HumanEmitter {
+    #[must_use]
+    pub const fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    fn paint(self, text: impl Display, style: Style) -> String {
+        if self.color {
+            text.style(style).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    const fn severity_style(severity: Severity) -> Style {
+        match severity {
+            Severity::Error => Style::new().red().bold(),
+            Severity::Warning => Style::new().yellow().bold(),
+            Severity::Info => Style::new().blue().bold(),
+            Severity::Hint => Style::new().cyan().bold(),
+        }
+    }
+
+    fn emit_snippet(
+        self,
+        diagnostic: &Diagnostic,
+        index: &LineIndex,
+        writer: &mut dyn Write,
+    ) -> io::Result<()> {
+        let start = diagnostic.start();
+        let end = diagnostic.end();
+
+        // Span which ends right after a line break doesn't touch the next line
+        let last_line = if end.line > start.line && end.column == 1 {
+            end.line - 1
+        } else {
+            end.line
+        };
+
+        let width = last_line.to_string().len();
+        let gutter = self.paint("|", Style::new().blue().bold());
+
+        writeln!(
+            writer,
+            "{:width$}{} {}:{}",
+            "",
+            self.paint("-->", Style::new().blue().bold()),
+            diagnostic.path().display(),
+            start
+        )?;
+        writeln!(writer, "{:width$} {gutter}", "")?;
+
+        for line in (start.line..=last_line).take(MAX_SNIPPET_LINES) {
+            let Some(text) = index.line(line) else {
+                break;
+            };
+
+            let from = if line == start.line { start.column } else { 1 };
+            let to = if line == end.line {
+                end.column
+            } else {
+                text.chars().count() + 1
+            };
+
+            // Keep tabs so carets stay aligned with the text above
+            let padding: String = text
+                .chars()
+                .take(from - 1)
+                .map(|char| if char == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let carets = "^".repeat(to.saturating_sub(from).max(1));
+
+            let source_line = format!(
+                "{} {gutter} {text}",
+                self.paint(format!("{line:>width$}"), Style::new().blue().bold())
+            );
+
+            writeln!(writer, "{}", source_line.trim_end())?;
+            writeln!(
+                writer,
+                "{:width$} {gutter} {padding}{}",
+                "",
+                self.paint(carets, Self::severity_style(diagnostic.severity()))
+            )?;
+        }
+
+        if last_line - start.line + 1 > MAX_SNIPPET_LINES {
+            writeln!(writer, "{:width$} {gutter} ...", "")?;
+        }
+
+        Ok(())
+    }
+
+    fn emit_diagnostic(
+        self,
+        diagnostic: &Diagnostic,
+        index: &LineIndex,
+        writer: &mut dyn Write,
+    ) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{}{}",
+            self.paint(
+                format!("{}[{}]: ", diagnostic.severity(), diagnostic.rule()),
+                Self::severity_style(diagnostic.severity())
+            ),
+            self.paint(diagnostic.message(), Style::new().bold())
+        )?;
+
+        self.emit_snippet(diagnostic, index, writer)?;
+        writeln!(writer)
+    }
+
+    fn emit_header(self, path: &Path, writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{}",
+            self.paint(path.display(), Style::new().bold().underline())
+        )
+    }
+
+    fn emit_summary(self, report: &Report, writer: &mut dyn Write) -> io::Result<()> {
+        if report.is_empty() {
+            return writeln!(
+                writer,
+                "{}",
+                self.paint("✓ No problems found", Style::new().green().bold())
+            );
+        }
+
+        let problems = report.len();
+        let notes = report.count_notes();
+
+        let by_severity = Severity::ALL
+            .into_iter()
+            .filter_map(|severity| {
+                let count = report
+                    .diagnostics()
+                    .filter(|diagnostic| diagnostic.severity() == severity)
+                    .count();
+
+                let plural = if count == 1 || severity == Severity::Info {
+                    ""
+                } else {
+                    "s"
+                };
+
+                (count > 0).then(|| format!("{count} {severity}{plural}"))
+            })
+            .join(", ");
+
+        writeln!(
+            writer,
+            "{}",
+            self.paint(
+                format!(
+                    "✘ {problems} problem{} ({by_severity}) in {notes} note{}",
+                    if problems == 1 { "" } else { "s" },
+                    if notes == 1 { "" } else { "s" }
+                ),
+                Style::new().red().bold()
+            )
+        )
+    }
+}
+
+impl Emitter for HumanEmitter {
+    fn emit(&self, report: &Report, writer: &mut dyn Write) -> io::Result<()> {
+        for (path, note) in report.notes() {
+            let index = LineIndex::new(note.source());
+
+            self.emit_header(path, writer)?;
+
+            for diagnostic in note.diagnostics() {
+                self.emit_diagnostic(diagnostic, &index, writer)?;
+            }
+        }
+
+        self.emit_summary(report, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::tests::note_report;
+    use obsidian_tidy_core::rule::Violation;
+    use std::path::PathBuf;
+
+    fn emit(report: &Report) -> String {
+        let mut output = Vec::new();
+        HumanEmitter::new(false).emit(report, &mut output).unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn empty_report() {
+        assert_eq!(emit(&Report::new()), "✓ No problems found\n");
+    }
+
+    #[test]
+    fn single_line() {
+        let report: Report = [(
+            PathBuf::from("notes/note.md"),
+            note_report(
+                "notes/note.md",
+                "# Title\n\ttext with problem\n",
+                &[("rule", Violation::new("Bad word", 19..26).unwrap())],
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            emit(&report),
+            "notes/note.md
+warning[rule]: Bad word
+ --> notes/note.md:2:12
+  |
+2 | \ttext with problem
+  | \t          ^^^^^^^
+
+✘ 1 problem (1 warning) in 1 note
+"
+        );
+    }
+
+    #[test]
+    fn multi_line() {
+        let report: Report = [(
+            PathBuf::from("note.md"),
+            note_report(
+                "note.md",
+                "first\nsecond\nthird",
+                &[("rule", Violation::new("Span", 3..9).unwrap())],
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            emit(&report),
+            "note.md
+warning[rule]: Span
+ --> note.md:1:4
+  |
+1 | first
+  |    ^^
+2 | second
+  | ^^^
+
+✘ 1 problem (1 warning) in 1 note
+"
+        );
+    }
+
+    #[test]
+    fn empty_location() {
+        let report: Report = [(
+            PathBuf::from("note.md"),
+            note_report(
+                "note.md",
+                "",
+                &[(
+                    "empty-content",
+                    Violation::new("Note is empty", 1..=1).unwrap(),
+                )],
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            emit(&report),
+            "note.md
+warning[empty-content]: Note is empty
+ --> note.md:1:1
+  |
+1 |
+  | ^
+
+✘ 1 problem (1 warning) in 1 note
+"
+        );
+    }
+
+    #[test]
+    fn summary_by_severity() {
+        use crate::diagnostic::{Diagnostic, NoteReport};
+        use crate::linter::RuleViolation;
+        use obsidian_tidy_core::rule::Category;
+
+        let source = "text";
+        let index = LineIndex::new(source);
+        let diagnostics = [Severity::Error, Severity::Info, Severity::Error]
+            .into_iter()
+            .map(|severity| {
+                let violation = RuleViolation {
+                    rule: "rule".to_string(),
+                    category: Category::Other,
+                    severity,
+                    violation: Violation::new("Problem", 0..4).unwrap(),
+                };
+
+                Diagnostic::new("note.md", &violation, &index)
+            })
+            .collect();
+
+        let report: Report = [(
+            PathBuf::from("note.md"),
+            NoteReport::new(source.to_string(), diagnostics),
+        )]
+        .into_iter()
+        .collect();
+
+        let output = emit(&report);
+
+        assert!(output.starts_with("note.md\nerror[rule]: Problem\n"));
+        assert!(output.ends_with("✘ 3 problems (2 errors, 1 info) in 1 note\n"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Output in JSON and JSON Lines
+
+use super::Emitter;
+use crate::diagnostic::Report;
+use std::io::{self, Write};
+
+/// Write all diagnostics as one JSON array
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonEmitter;
+
+impl Emitter for JsonEmitter {
+    fn emit(&self, report: &Report, writer: &mut dyn Write) -> io::Result<()> {
+        let diagnostics: Vec<_> = report.diagnostics().collect();
+
+        serde_json::to_writer_pretty(&mut *writer, &diagnostics)?;
+        writeln!(writer)
+    }
+}
+
+/// Write one diagnostic per line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonLinesEmitter;
+
+impl Emitter for JsonLinesEmitter {
+    fn emit(&self, report: &Report, writer: &mut dyn Write) -> io::Result<()> {
+        for diagnostic in report.diagnostics() {
+            serde_json::to_writer(&mut *writer, diagnostic)?;
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::tests::note_report;
+    use obsidian_tidy_core::rule::Violation;
+    use serde_json::{Value, json};
+    use std::path::PathBuf;
+
+    fn report() -> Report {
+        [(
+            PathBuf::from("note.md"),
+            note_report(
+                "note.md",
+                "first\nsecond",
+                &[
+                    ("rule-a", Violation::new("First", 0..5).unwrap()),
+                    ("rule-b", Violation::new("Second", 6..12).unwrap()),
+                ],
+            ),
+        )]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn json() {
+        let mut output = Vec::new();
+        JsonEmitter.emit(&report(), &mut output).unwrap();
+
+        let value: Value = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(
+            value[0],
+            json!({
+                "path": "note.md",
+                "rule": "rule-a",
+                "category": "content",
+                "severity": "warning",
+                "message": "First",
+                "location": { "start": 0, "end": 5 },
+                "start": { "line": 1, "column": 1 },
+                "end": { "line": 1, "column": 6 },
+            })
+        );
+        assert_eq!(value.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn json_lines() {
+        let mut output = Vec::new();
+        JsonLinesEmitter.emit(&report(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["rule"], "rule-b");
+        assert_eq!(lines[1]["start"], json!({ "line": 2, "column": 1 }));
+    }
+
+    #[test]
+    fn json_empty() {
+        let mut output = Vec::new();
+        JsonEmitter.emit(&Report::new(), &mut output).unwrap();
+
+        assert_eq!(output, b"[]\n");
+    }
+}
@@ -0,0 +1,36 @@
+//! Output of [`Report`](super::Report)
+
+pub mod human;
+pub mod json;
+pub mod sarif;
+
+use super::Report;
+use clap::ValueEnum;
+use std::io::{self, Write};
+
+pub use human::HumanEmitter;
+pub use json::{JsonEmitter, JsonLinesEmitter};
+pub use sarif::SarifEmitter;
+
+/// Write report to output
+pub trait Emitter {
+    fn emit(&self, report: &Report, writer: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Format of output for diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Human-readable with source snippets
+    #[default]
+    Human,
+
+    /// Array of diagnostics in JSON
+    Json,
+
+    /// One JSON diagnostic per line
+    JsonLines,
+
+    /// SARIF 2.1.0 for code-scanning tools
+    Sarif,
+}
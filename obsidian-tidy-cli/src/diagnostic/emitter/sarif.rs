@@ -0,0 +1,253 @@
+//! Output in SARIF 2.1.0
+//!
+//! Specification: <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>
+
+use super::Emitter;
+use crate::diagnostic::{Diagnostic, Report};
+use obsidian_tidy_core::rule::Severity;
+use serde_json::{Value, json};
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/CryptoGladi/obsidian-tidy";
+
+/// Id of base uri for paths of notes
+const VAULT_BASE_ID: &str = "VAULT";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SarifEmitter {
+    root: PathBuf,
+
+    /// Name and description of rules
+    rules: BTreeMap<String, String>,
+}
+
+impl SarifEmitter {
+    /// `root` is path to vault. `rules` are pairs of name and description
+    #[must_use]
+    pub fn new(
+        root: impl Into<PathBuf>,
+        rules: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        Self {
+            root: root.into(),
+            rules: rules.into_iter().collect(),
+        }
+    }
+
+    fn rules(&self) -> Value {
+        self.rules
+            .iter()
+            .map(|(name, description)| {
+                json!({
+                    "id": name,
+                    "shortDescription": { "text": description },
+                })
+            })
+            .collect()
+    }
+
+    fn result(&self, diagnostic: &Diagnostic) -> Value {
+        let location = diagnostic.location();
+        let mut result = json!({
+            "ruleId": diagnostic.rule(),
+            "level": level(diagnostic.severity()),
+            "message": { "text": diagnostic.message() },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": {
+                        "uri": encode_path(diagnostic.path()),
+                        "uriBaseId": VAULT_BASE_ID,
+                    },
+                    "region": {
+                        "startLine": diagnostic.start().line,
+                        "startColumn": diagnostic.start().column,
+                        "endLine": diagnostic.end().line,
+                        "endColumn": diagnostic.end().column,
+                        "byteOffset": location.start,
+                        "byteLength": location.len(),
+                    },
+                },
+            }],
+        });
+
+        if let Some(index) = self.rules.keys().position(|name| name == diagnostic.rule()) {
+            result["ruleIndex"] = index.into();
+        }
+
+        result
+    }
+
+    fn root_uri(&self) -> String {
+        let root = std::path::absolute(&self.root).unwrap_or_else(|_| self.root.clone());
+        let mut uri = encode_path(&root);
+
+        if !uri.starts_with('/') {
+            uri.insert(0, '/');
+        }
+
+        if !uri.ends_with('/') {
+            uri.push('/');
+        }
+
+        format!("file://{uri}")
+    }
+}
+
+/// SARIF has no levels for info and hint, both are notes
+const fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "note",
+    }
+}
+
+/// Convert path to uri reference with `/` as separator
+fn encode_path(path: &Path) -> String {
+    let mut uri = String::new();
+
+    for component in path.components() {
+        match component {
+            Component::Prefix(prefix) => uri.push_str(&prefix.as_os_str().to_string_lossy()),
+            Component::RootDir => uri.push('/'),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                push_separator(&mut uri);
+                uri.push_str("..");
+            }
+            Component::Normal(segment) => {
+                push_separator(&mut uri);
+                uri.push_str(&encode_segment(&segment.to_string_lossy()));
+            }
+        }
+    }
+
+    uri
+}
+
+fn push_separator(uri: &mut String) {
+    if !uri.is_empty() && !uri.ends_with('/') {
+        uri.push('/');
+    }
+}
+
+/// Percent-encode all except unreserved characters (RFC 3986)
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+
+    encoded
+}
+
+impl Emitter for SarifEmitter {
+    fn emit(&self, report: &Report, writer: &mut dyn Write) -> io::Result<()> {
+        let results: Vec<_> = report
+            .diagnostics()
+            .map(|diagnostic| self.result(diagnostic))
+            .collect();
+
+        let sarif = json!({
+            "$schema": SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "obsidian-tidy",
+                        "informationUri": INFORMATION_URI,
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": self.rules(),
+                    },
+                },
+                "originalUriBaseIds": {
+                    VAULT_BASE_ID: { "uri": self.root_uri() },
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        });
+
+        serde_json::to_writer_pretty(&mut *writer, &sarif)?;
+        writeln!(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::tests::note_report;
+    use obsidian_tidy_core::rule::Violation;
+
+    #[test]
+    fn encode() {
+        assert_eq!(
+            encode_path(Path::new("notes/my note.md")),
+            "notes/my%20note.md"
+        );
+        assert_eq!(
+            encode_path(Path::new("./заметка.md")),
+            "%D0%B7%D0%B0%D0%BC%D0%B5%D1%82%D0%BA%D0%B0.md"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn root_uri() {
+        let emitter = SarifEmitter::new("/home/user/my vault", []);
+        assert_eq!(emitter.root_uri(), "file:///home/user/my%20vault/");
+    }
+
+    #[test]
+    fn emit() {
+        let report: Report = [(
+            PathBuf::from("notes/note.md"),
+            note_report(
+                "notes/note.md",
+                "first\nsecond",
+                &[("rule-b", Violation::new("Problem", 6..12).unwrap())],
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        let emitter = SarifEmitter::new(
+            "vault",
+            [
+                ("rule-a".to_string(), "First rule".to_string()),
+                ("rule-b".to_string(), "Second rule".to_string()),
+            ],
+        );
+
+        let mut output = Vec::new();
+        emitter.emit(&report, &mut output).unwrap();
+
+        let sarif: Value = serde_json::from_slice(&output).unwrap();
+        let run = &sarif["runs"][0];
+        let result = &run["results"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "rule-b");
+        assert_eq!(result["ruleId"], "rule-b");
+        assert_eq!(result["ruleIndex"], 1);
+        assert_eq!(result["level"], "warning");
+        assert_eq!(result["message"]["text"], "Problem");
+
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "notes/note.md");
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["endColumn"], 7);
+        assert_eq!(location["region"]["byteLength"], 6);
+    }
+}
@@ -0,0 +1,280 @@
+//! Diagnostics for output of violations
+
+pub mod emitter;
+pub mod position;
+
+use crate::linter::RuleViolation;
+use obsidian_tidy_core::rule::{Category, Severity};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+pub use emitter::{Emitter, OutputFormat};
+pub use position::{LineIndex, Position};
+
+/// Violation of rule with resolved position in note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    path: PathBuf,
+    rule: String,
+    category: Category,
+    severity: Severity,
+    message: String,
+    location: Range<usize>,
+    start: Position,
+    end: Position,
+}
+
+impl Diagnostic {
+    /// Create diagnostic from violation.
+    /// `path` must be relative to the vault
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, violation: &RuleViolation, index: &LineIndex) -> Self {
+        let location = violation.violation.location().clone();
+
+        Self {
+            path: path.into(),
+            rule: violation.rule.clone(),
+            category: violation.category.clone(),
+            severity: violation.severity,
+            message: violation.violation.message().to_string(),
+            start: index.position(location.start),
+            end: index.position(location.end),
+            location,
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    #[must_use]
+    pub const fn category(&self) -> &Category {
+        &self.category
+    }
+
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[must_use]
+    pub const fn location(&self) -> &Range<usize> {
+        &self.location
+    }
+
+    #[must_use]
+    pub const fn start(&self) -> Position {
+        self.start
+    }
+
+    #[must_use]
+    pub const fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Key for deterministic sorting
+    fn sort_key(&self) -> (Position, Position, &str, &str) {
+        (self.start, self.end, &self.rule, &self.message)
+    }
+}
+
+/// Diagnostics of one note with it source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteReport {
+    source: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl NoteReport {
+    #[must_use]
+    pub fn new(source: String, mut diagnostics: Vec<Diagnostic>) -> Self {
+        diagnostics.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+
+        Self {
+            source,
+            diagnostics,
+        }
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// All diagnostics of check grouped per note.
+/// Notes are sorted by path
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    notes: BTreeMap<PathBuf, NoteReport>,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add report of note. Notes without diagnostics are skipped
+    pub fn insert(&mut self, path: impl Into<PathBuf>, note: NoteReport) {
+        if !note.diagnostics.is_empty() {
+            self.notes.insert(path.into(), note);
+        }
+    }
+
+    pub fn notes(&self) -> impl Iterator<Item = (&Path, &NoteReport)> {
+        self.notes.iter().map(|(path, note)| (path.as_path(), note))
+    }
+
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.notes.values().flat_map(NoteReport::diagnostics)
+    }
+
+    /// Count of notes with diagnostics
+    #[must_use]
+    pub fn count_notes(&self) -> usize {
+        self.notes.len()
+    }
+
+    /// Count of all diagnostics
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.notes.values().map(|note| note.diagnostics.len()).sum()
+    }
+
+    /// Count of diagnostics with severity `severity` or higher
+    #[must_use]
+    pub fn count_at_least(&self, severity: Severity) -> usize {
+        self.diagnostics()
+            .filter(|diagnostic| diagnostic.severity() >= severity)
+            .count()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+}
+
+impl FromIterator<(PathBuf, NoteReport)> for Report {
+    fn from_iter<T: IntoIterator<Item = (PathBuf, NoteReport)>>(iter: T) -> Self {
+        let mut report = Self::new();
+
+        for (path, note) in iter {
+            report.insert(path, note);
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use obsidian_tidy_core::rule::Violation;
+
+    pub(crate) fn rule_violation(rule: &str, violation: Violation) -> RuleViolation {
+        RuleViolation {
+            rule: rule.to_string(),
+            category: Category::Content,
+            severity: Severity::Warning,
+            violation,
+        }
+    }
+
+    pub(crate) fn note_report(
+        path: &str,
+        source: &str,
+        violations: &[(&str, Violation)],
+    ) -> NoteReport {
+        let index = LineIndex::new(source);
+        let diagnostics = violations
+            .iter()
+            .map(|(rule, violation)| {
+                Diagnostic::new(path, &rule_violation(rule, violation.clone()), &index)
+            })
+            .collect();
+
+        NoteReport::new(source.to_string(), diagnostics)
+    }
+
+    #[test]
+    fn diagnostic_position() {
+        let source = "# Title\n\ntext with problem\n";
+        let violation = rule_violation("rule", Violation::new("Problem", 19..26).unwrap());
+        let diagnostic = Diagnostic::new("note.md", &violation, &LineIndex::new(source));
+
+        assert_eq!(
+            diagnostic.start(),
+            Position {
+                line: 3,
+                column: 11
+            }
+        );
+        assert_eq!(
+            diagnostic.end(),
+            Position {
+                line: 3,
+                column: 18
+            }
+        );
+    }
+
+    #[test]
+    fn sorted() {
+        let report: Report = [
+            (
+                PathBuf::from("b.md"),
+                note_report(
+                    "b.md",
+                    "text\ntext",
+                    &[
+                        ("rule-b", Violation::new("Second", 5..6).unwrap()),
+                        ("rule-a", Violation::new("First", 0..1).unwrap()),
+                    ],
+                ),
+            ),
+            (
+                PathBuf::from("a.md"),
+                note_report(
+                    "a.md",
+                    "text",
+                    &[("rule", Violation::new("Problem", 0..1).unwrap())],
+                ),
+            ),
+            (PathBuf::from("c.md"), note_report("c.md", "text", &[])),
+        ]
+        .into_iter()
+        .collect();
+
+        let paths: Vec<_> = report.notes().map(|(path, _)| path).collect();
+        let messages: Vec<_> = report.diagnostics().map(Diagnostic::message).collect();
+
+        assert_eq!(paths, [Path::new("a.md"), Path::new("b.md")]);
+        assert_eq!(messages, ["Problem", "First", "Second"]);
+        assert_eq!(report.len(), 3);
+        assert_eq!(report.count_notes(), 2);
+        assert_eq!(report.count_at_least(Severity::Warning), 3);
+        assert_eq!(report.count_at_least(Severity::Error), 0);
+    }
+}
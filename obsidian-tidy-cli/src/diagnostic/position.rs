@@ -0,0 +1,164 @@
+//! Convert byte offsets of a note to line/column positions
+
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Position in a note. Both line and column start at 1.
+/// Column is counted in characters, not in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Index of line starts for fast lookup of [`Position`] by byte offset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    #[must_use]
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Count of lines in source
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Clamp offset to source length and to a char boundary
+    fn clamp(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.source.len());
+
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        offset
+    }
+
+    /// Zero-based index of line which contains `offset`
+    fn line_index(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        }
+    }
+
+    /// Get [`Position`] by byte offset.
+    /// Offsets outside of source are clamped to the end of source
+    #[must_use]
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = self.clamp(offset);
+        let line = self.line_index(offset);
+        let column = self.source[self.line_starts[line]..offset].chars().count() + 1;
+
+        Position {
+            line: line + 1,
+            column,
+        }
+    }
+
+    /// Like [`Self::position`], but column is counted in UTF-16 code units as in LSP
+    #[must_use]
+    pub fn utf16_position(&self, offset: usize) -> Position {
+        let offset = self.clamp(offset);
+        let line = self.line_index(offset);
+        let column = self.source[self.line_starts[line]..offset]
+            .encode_utf16()
+            .count()
+            + 1;
+
+        Position {
+            line: line + 1,
+            column,
+        }
+    }
+
+    /// Get text of line without line ending. `line` starts at 1
+    #[must_use]
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.source.len());
+
+        let text = &self.source[start..end];
+        let text = text.strip_suffix('\n').unwrap_or(text);
+
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf16_position() {
+        let index = LineIndex::new("ж😀x\nend");
+
+        assert_eq!(index.utf16_position(6), Position { line: 1, column: 4 });
+        assert_eq!(index.position(6), Position { line: 1, column: 3 });
+        assert_eq!(index.utf16_position(8), Position { line: 2, column: 1 });
+    }
+
+    #[test]
+    fn position() {
+        let index = LineIndex::new("first\nsecond\n\nlast");
+
+        assert_eq!(index.position(0), Position { line: 1, column: 1 });
+        assert_eq!(index.position(5), Position { line: 1, column: 6 });
+        assert_eq!(index.position(6), Position { line: 2, column: 1 });
+        assert_eq!(index.position(13), Position { line: 3, column: 1 });
+        assert_eq!(index.position(17), Position { line: 4, column: 4 });
+    }
+
+    #[test]
+    fn position_out_of_range() {
+        let index = LineIndex::new("abc");
+        assert_eq!(index.position(100), Position { line: 1, column: 4 });
+    }
+
+    #[test]
+    fn position_unicode() {
+        let index = LineIndex::new("привет мир");
+
+        // 'м' starts at byte 13
+        assert_eq!(index.position(13), Position { line: 1, column: 8 });
+
+        // Inside of 'м'
+        assert_eq!(index.position(14), Position { line: 1, column: 8 });
+    }
+
+    #[test]
+    fn line() {
+        let index = LineIndex::new("first\r\nsecond\n");
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line(1), Some("first"));
+        assert_eq!(index.line(2), Some("second"));
+        assert_eq!(index.line(3), Some(""));
+        assert_eq!(index.line(0), None);
+        assert_eq!(index.line(4), None);
+    }
+}
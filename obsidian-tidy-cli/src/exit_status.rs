@@ -0,0 +1,94 @@
+//! Exit status of process
+
+use std::process::ExitCode;
+
+/// Exit status of obsidian-tidy.
+/// Used by scripts (pre-commit, CI) for gating
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExitStatus {
+    /// Command done without problems
+    Success = 0,
+
+    /// Violations found
+    Violations = 1,
+
+    /// Rule failed while checking
+    RuleFailed = 2,
+
+    /// Config not found or invalid
+    ConfigError = 3,
+
+    /// Any other error
+    Failure = 4,
+}
+
+/// Error is a mistake in config. Invalid exclude pattern is a mistake in config too
+fn is_config_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    error.is::<obsidian_tidy_config::Error>() || error.is::<obsidian_tidy_core::discovery::Error>()
+}
+
+impl ExitStatus {
+    /// Get status for error returned by command
+    ///
+    /// # Example
+    /// ```
+    /// use obsidian_tidy_cli::ExitStatus;
+    /// use obsidian_tidy_config::Error as ConfigError;
+    ///
+    /// let error = ConfigError::IO(std::io::ErrorKind::NotFound.into());
+    /// assert_eq!(ExitStatus::from_error(&error), ExitStatus::ConfigError);
+    /// ```
+    #[must_use]
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self {
+        if std::iter::successors(Some(error), |error| error.source()).any(is_config_error) {
+            Self::ConfigError
+        } else {
+            Self::Failure
+        }
+    }
+
+    #[must_use]
+    pub const fn is_success(self) -> bool {
+        matches!(self, Self::Success)
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        Self::from(status as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use thiserror::Error;
+
+    #[derive(Debug, Error)]
+    enum Error {
+        #[error("Config: {0}")]
+        Config(#[from] obsidian_tidy_config::Error),
+
+        #[error("Other")]
+        Other,
+    }
+
+    #[test]
+    fn from_error() {
+        let config_error = Error::Config(obsidian_tidy_config::Error::IO(
+            std::io::ErrorKind::NotFound.into(),
+        ));
+
+        assert_eq!(
+            ExitStatus::from_error(&config_error),
+            ExitStatus::ConfigError
+        );
+        assert_eq!(ExitStatus::from_error(&Error::Other), ExitStatus::Failure);
+    }
+
+    #[test]
+    fn is_success() {
+        assert!(ExitStatus::Success.is_success());
+        assert!(!ExitStatus::Violations.is_success());
+    }
+}
@@ -1,15 +1,28 @@
 //! Module for CLI interfaic
 
+pub mod cache;
 pub mod command;
+pub mod diagnostic;
+pub mod exit_status;
+pub mod linter;
 pub mod logger_config;
+pub mod lsp;
+pub mod suppression;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 use clap::ValueHint;
 use std::path::PathBuf;
 
 pub use clap::Parser;
 pub use command::Command;
+pub use exit_status::ExitStatus;
 pub use logger_config::{LogLevel, LoggerConfig};
 
+/// Name of config file in vault
+pub const CONFIG_FILE: &str = ".obsidian-tidy.toml";
+
 /// Returns the current working directory
 fn current_dir() -> PathBuf {
     std::env::current_dir().unwrap_or(PathBuf::from("."))
@@ -38,6 +51,10 @@ pub struct Cli {
     #[arg(long, value_name = "DIRECTORY", value_hint = ValueHint::DirPath, value_parser = existing_dir, default_value = current_dir().into_os_string())]
     pub path: PathBuf,
 
+    /// Lint all notes: don't use `.gitignore`, `.obtidyignore` and `exclude` from config
+    #[arg(long, global = true)]
+    pub no_ignore: bool,
+
     /// Logger options
     #[command(flatten, next_help_heading = "Logger options")]
     pub logger: LoggerConfig,
@@ -51,6 +68,6 @@ impl Cli {
     /// Return path to config
     #[must_use]
     pub fn config(&self) -> PathBuf {
-        self.path.join(".obsidian-tidy.toml")
+        self.path.join(CONFIG_FILE)
     }
 }
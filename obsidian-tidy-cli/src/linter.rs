@@ -0,0 +1,260 @@
+//! Run rules of config on notes
+
+use crate::diagnostic::{Diagnostic, LineIndex, NoteReport};
+use crate::suppression;
+use obsidian_parser::prelude::Note as _;
+use obsidian_tidy_config::Config;
+use obsidian_tidy_core::{
+    Note,
+    rule::{Category, Content, Rule, Severity, SharedErrorRule, ToggleableRule, Violation},
+    tags,
+};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{instrument, warn};
+
+/// Rule which returned error instead of violations
+#[derive(Debug, Clone)]
+pub struct RuleFailure {
+    pub rule: String,
+    pub error: Arc<dyn std::error::Error + Send + Sync>,
+}
+
+/// [`Violation`] with info about rule which found it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleViolation {
+    pub rule: String,
+    pub category: Category,
+    pub severity: Severity,
+    pub violation: Violation,
+}
+
+/// Result of check one note
+#[derive(Debug, Default, Clone)]
+pub struct NoteCheck {
+    pub violations: Vec<RuleViolation>,
+
+    /// Violations which are suppressed by comments or frontmatter of note
+    pub suppressed: Vec<RuleViolation>,
+
+    pub failures: Vec<RuleFailure>,
+}
+
+impl NoteCheck {
+    /// Build report from violations. `path` must be relative to the vault
+    #[must_use]
+    pub fn report(&self, path: &Path, source: String) -> NoteReport {
+        let index = LineIndex::new(&source);
+        let diagnostics = self
+            .violations
+            .iter()
+            .map(|violation| Diagnostic::new(path, violation, &index))
+            .collect();
+
+        NoteReport::new(source, diagnostics)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Linter<'a> {
+    config: &'a Config,
+    root: &'a Path,
+}
+
+impl<'a> Linter<'a> {
+    /// `root` is path to vault
+    #[must_use]
+    pub const fn new(config: &'a Config, root: &'a Path) -> Self {
+        Self { config, root }
+    }
+
+    /// Path of note relative to the vault
+    #[must_use]
+    pub fn relative_path(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(self.root).unwrap_or(path).to_path_buf()
+    }
+
+    /// Config ignores notes by tags, so tags of notes must be found
+    fn uses_tags(config: &Config) -> bool {
+        !config.general().ignore_tags.is_empty()
+            || config
+                .rules()
+                .iter()
+                .any(|rule| !rule.ignore_tags().is_empty())
+    }
+
+    /// Run all rules of config for note
+    #[instrument(skip_all)]
+    pub fn check(&self, content: &Content, note: &Note) -> NoteCheck {
+        self.check_with(content, note, |_| None)
+    }
+
+    /// Run rules of config for note. Config of subfolder of note is used, if there is one.
+    /// Rules whose globs don't match path of note or whose ignored tags are in note are skipped.
+    /// If `cached` returns violations of rule, then the rule isn't run.
+    /// Violations suppressed by note are moved to [`NoteCheck::suppressed`]
+    #[instrument(skip_all)]
+    pub fn check_with<F>(&self, content: &Content, note: &Note, cached: F) -> NoteCheck
+    where
+        F: Fn(&ToggleableRule<SharedErrorRule>) -> Option<Vec<Violation>>,
+    {
+        let mut result = NoteCheck::default();
+        let path = note.path().map(|path| self.relative_path(&path));
+        let source = content
+            .source(note)
+            .inspect_err(|error| warn!("Failed read note: {error}"))
+            .ok();
+
+        let config = path
+            .as_deref()
+            .map_or(self.config, |path| self.config.for_note(path));
+
+        let tags = match &source {
+            Some(source) if Self::uses_tags(config) => tags::find(source),
+            _ => BTreeSet::new(),
+        };
+
+        if tags::contains_any(&tags, &config.general().ignore_tags) {
+            return result;
+        }
+
+        let mut checked = Vec::new();
+
+        for rule in config.rules().iter() {
+            if let Some(path) = &path
+                && !rule.applies_to(path)
+            {
+                continue;
+            }
+
+            if rule.ignores_tags(&tags) {
+                continue;
+            }
+
+            let violations = match cached(rule) {
+                Some(violations) => Ok(violations),
+                None => rule.check(content, note),
+            };
+
+            match violations {
+                Ok(violations) => {
+                    if rule.is_enabled() {
+                        checked.push(rule.name());
+                    }
+
+                    result
+                        .violations
+                        .extend(violations.into_iter().map(|violation| RuleViolation {
+                            rule: rule.name().to_string(),
+                            category: rule.category(),
+                            severity: rule.severity(),
+                            violation,
+                        }));
+                }
+                Err(error) => result.failures.push(RuleFailure {
+                    rule: rule.name().to_string(),
+                    error,
+                }),
+            }
+        }
+
+        if let Some(source) = &source {
+            suppression::apply(source, &mut result, &checked);
+        }
+
+        result
+    }
+
+    /// Read source of note from disk for rendering snippets
+    #[must_use]
+    pub fn read_source(note: &Note) -> String {
+        let Some(path) = note.path() else {
+            return String::new();
+        };
+
+        std::fs::read_to_string(&path).unwrap_or_else(|error| {
+            warn!("Failed read note `{}`: {error}", path.display());
+            String::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::DoubleSpace;
+    use obsidian_tidy_config::{General, builder::ConfigBuilder};
+    use obsidian_tidy_core::rule::{PathFilter, Rules, content::parse_note};
+
+    #[test]
+    fn skip_rule_by_paths() {
+        let paths = PathFilter::new(Vec::<String>::new(), ["daily/**"]).unwrap();
+        let rule = ToggleableRule::new(SharedErrorRule::new(DoubleSpace), true).with_paths(paths);
+        let config = ConfigBuilder::default()
+            .rules(Rules::new(vec![rule]).unwrap())
+            .build();
+
+        let root = Path::new("/vault");
+        let linter = Linter::new(&config, root);
+
+        let check = |path: &str| {
+            let path = root.join(path);
+            let content = Content::default().with_source(&path, "a  b");
+            let note = parse_note("a  b", path).unwrap();
+
+            linter.check(&content, &note).violations.len()
+        };
+
+        assert_eq!(check("daily/note.md"), 0);
+        assert_eq!(check("projects/note.md"), 1);
+    }
+
+    #[test]
+    fn suppressed() {
+        let config = crate::test_utils::config();
+        let path = Path::new("/vault/note.md");
+        let source = "<!-- obsidian-tidy-disable-file double-space -->\na  b";
+
+        let content = Content::default().with_source(path, source);
+        let note = parse_note(source, path).unwrap();
+        let check = Linter::new(&config, Path::new("/vault")).check(&content, &note);
+
+        assert!(check.violations.is_empty());
+        assert_eq!(check.suppressed.len(), 1);
+    }
+
+    #[test]
+    fn skip_by_tags() {
+        let rule = ToggleableRule::new(SharedErrorRule::new(DoubleSpace), true)
+            .with_ignore_tags(vec!["#no-lint".to_string()]);
+        let rule_config = ConfigBuilder::default()
+            .rules(Rules::new(vec![rule]).unwrap())
+            .build();
+        let general_config = ConfigBuilder::default()
+            .rules(crate::test_utils::config().rules().clone())
+            .general(General {
+                ignore_tags: vec!["draft".to_string()],
+                ..General::default()
+            })
+            .build();
+
+        let check = |config: &Config, source: &str| {
+            let path = Path::new("/vault/note.md");
+            let content = Content::default().with_source(path, source);
+            let note = parse_note(source, path).unwrap();
+
+            Linter::new(config, Path::new("/vault"))
+                .check(&content, &note)
+                .violations
+                .len()
+        };
+
+        assert_eq!(check(&rule_config, "a  b #no-lint"), 0);
+        assert_eq!(check(&rule_config, "a  b #draft"), 1);
+        assert_eq!(check(&general_config, "---\ntags: [draft]\n---\na  b"), 0);
+        assert_eq!(check(&general_config, "a  b"), 1);
+    }
+}
@@ -0,0 +1,80 @@
+//! Convert diagnostics and fixes to LSP types
+
+use crate::diagnostic::LineIndex;
+use crate::linter::RuleViolation;
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range, TextEdit};
+use obsidian_tidy_core::rule::{Fix, Severity};
+
+/// Name of diagnostics source shown by editors
+pub const SOURCE: &str = "obsidian-tidy";
+
+/// LSP position by byte offset. LSP lines and columns start at 0
+#[must_use]
+pub fn position(index: &LineIndex, offset: usize) -> Position {
+    let position = index.utf16_position(offset);
+
+    Position::new(
+        u32::try_from(position.line - 1).unwrap_or(u32::MAX),
+        u32::try_from(position.column - 1).unwrap_or(u32::MAX),
+    )
+}
+
+#[must_use]
+pub fn range(index: &LineIndex, location: &std::ops::Range<usize>) -> Range {
+    Range::new(
+        position(index, location.start),
+        position(index, location.end),
+    )
+}
+
+#[must_use]
+pub const fn severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+        Severity::Hint => DiagnosticSeverity::HINT,
+    }
+}
+
+#[must_use]
+pub fn diagnostic(index: &LineIndex, violation: &RuleViolation) -> Diagnostic {
+    Diagnostic {
+        range: range(index, violation.violation.location()),
+        severity: Some(severity(violation.severity)),
+        code: Some(NumberOrString::String(violation.rule.clone())),
+        source: Some(SOURCE.to_string()),
+        message: violation.violation.message().to_string(),
+        ..Diagnostic::default()
+    }
+}
+
+#[must_use]
+pub fn text_edits(index: &LineIndex, fix: &Fix) -> Vec<TextEdit> {
+    fix.edits()
+        .iter()
+        .map(|edit| TextEdit::new(range(index, edit.range()), edit.replacement().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::tests::rule_violation;
+    use obsidian_tidy_core::rule::Violation;
+
+    #[test]
+    fn utf16_range() {
+        let index = LineIndex::new("😀 text\nsecond");
+        let violation = rule_violation("rule", Violation::new("Problem", 5..13).unwrap());
+
+        let diagnostic = diagnostic(&index, &violation);
+
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(0, 3), Position::new(1, 3))
+        );
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostic.code, Some(NumberOrString::String("rule".into())));
+    }
+}
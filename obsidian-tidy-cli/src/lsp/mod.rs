@@ -0,0 +1,706 @@
+//! Language server for editors
+//!
+//! Publishes the same diagnostics as `check` and offers code actions for fixes
+
+pub mod convert;
+
+use crate::command::{discovery, load_config};
+use crate::diagnostic::LineIndex;
+use crate::linter::{Linter, RuleViolation};
+use lsp_server::{
+    Connection, ErrorCode, Message, Notification, ProtocolError, Request, RequestId, Response,
+};
+use lsp_types::{
+    ClientCapabilities, CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, FileSystemWatcher, GlobPattern,
+    LogMessageParams, MessageType, PositionEncodingKind, PublishDiagnosticsParams, Range,
+    Registration, RegistrationParams, ServerCapabilities, ShowMessageParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, TextEdit, Url, WorkspaceEdit,
+    notification::{
+        DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+        DidSaveTextDocument, LogMessage, Notification as _, PublishDiagnostics, ShowMessage,
+    },
+    request::{CodeActionRequest, RegisterCapability, Request as _},
+};
+use obsidian_tidy_config::Config;
+use obsidian_tidy_core::{
+    discovery::{Discovery, Error as DiscoveryError, IGNORE_FILE},
+    rule::{Applicability, Content, content::parse_note, fix::apply_fixes},
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    #[error("Connection with client is closed")]
+    Disconnected,
+}
+
+/// Document opened in editor
+#[derive(Debug, Clone, Default)]
+struct Document {
+    text: String,
+    violations: Vec<RuleViolation>,
+}
+
+#[derive(Debug)]
+pub struct Server {
+    root: PathBuf,
+    config: Config,
+    no_ignore: bool,
+    discovery: Discovery,
+    content: Content,
+    documents: HashMap<Url, Document>,
+}
+
+fn notification(method: &str, params: impl Serialize) -> Message {
+    Notification::new(method.to_string(), params).into()
+}
+
+/// Message which editor shows to user
+pub fn show_message(typ: MessageType, message: impl Into<String>) -> Message {
+    notification(
+        ShowMessage::METHOD,
+        ShowMessageParams {
+            typ,
+            message: message.into(),
+        },
+    )
+}
+
+fn log_message(typ: MessageType, message: impl Into<String>) -> Message {
+    notification(
+        LogMessage::METHOD,
+        LogMessageParams {
+            typ,
+            message: message.into(),
+        },
+    )
+}
+
+/// Id of registration of watchers of configs and ignore files
+const WATCHERS_REGISTRATION: &str = "obsidian-tidy-watchers";
+
+/// Files which change config or ignored notes
+const WATCHED_FILES: [&str; 3] = [crate::CONFIG_FILE, IGNORE_FILE, ".gitignore"];
+
+/// Request to client to notify about changes of configs and ignore files made outside of editor.
+/// `None` if client can't register watchers
+#[must_use]
+pub fn register_watchers(capabilities: &ClientCapabilities) -> Option<Message> {
+    let dynamic_registration = capabilities
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.did_change_watched_files)
+        .and_then(|watched_files| watched_files.dynamic_registration)
+        .unwrap_or_default();
+
+    if !dynamic_registration {
+        return None;
+    }
+
+    let watchers = WATCHED_FILES
+        .into_iter()
+        .map(|name| FileSystemWatcher {
+            glob_pattern: GlobPattern::String(format!("**/{name}")),
+            kind: None,
+        })
+        .collect();
+
+    let params = RegistrationParams {
+        registrations: vec![Registration {
+            id: WATCHERS_REGISTRATION.to_string(),
+            method: DidChangeWatchedFiles::METHOD.to_string(),
+            register_options: serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
+                watchers,
+            })
+            .ok(),
+        }],
+    };
+
+    Some(
+        Request::new(
+            RequestId::from(WATCHERS_REGISTRATION.to_string()),
+            RegisterCapability::METHOD.to_string(),
+            params,
+        )
+        .into(),
+    )
+}
+
+fn workspace_edit(uri: &Url, edits: Vec<TextEdit>) -> WorkspaceEdit {
+    WorkspaceEdit {
+        changes: Some(HashMap::from([(uri.clone(), edits)])),
+        ..WorkspaceEdit::default()
+    }
+}
+
+fn intersects(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+impl Server {
+    /// `root` is path to vault. `no_ignore` - lint notes which are ignored by config or ignore files
+    pub fn new(
+        root: impl Into<PathBuf>,
+        config: Config,
+        no_ignore: bool,
+    ) -> Result<Self, DiscoveryError> {
+        let root = root.into();
+        let discovery = discovery(&root, &config, no_ignore)?;
+
+        Ok(Self {
+            content: Content::from_files(&root, &discovery.notes()),
+            root,
+            config,
+            no_ignore,
+            discovery,
+            documents: HashMap::new(),
+        })
+    }
+
+    #[must_use]
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            position_encoding: Some(PositionEncodingKind::UTF16),
+            text_document_sync: Some(TextDocumentSyncCapability::Options(
+                TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::FULL),
+                    save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    ..TextDocumentSyncOptions::default()
+                },
+            )),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            ..ServerCapabilities::default()
+        }
+    }
+
+    fn config_path(&self) -> PathBuf {
+        self.root.join(crate::CONFIG_FILE)
+    }
+
+    /// Config or ignore file of vault or of its subfolder
+    fn is_watched(&self, uri: &Url) -> bool {
+        uri.to_file_path().is_ok_and(|path| {
+            path.starts_with(&self.root)
+                && path
+                    .file_name()
+                    .is_some_and(|name| WATCHED_FILES.iter().any(|watched| name == *watched))
+        })
+    }
+
+    /// Handle messages until client asks to shutdown
+    #[instrument(skip_all, err)]
+    pub fn run(&mut self, connection: &Connection) -> Result<(), Error> {
+        for message in &connection.receiver {
+            let responses = match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        debug!("Shutdown language server");
+                        return Ok(());
+                    }
+
+                    vec![self.handle_request(request).into()]
+                }
+                Message::Notification(notification) => self.handle_notification(notification),
+                Message::Response(_) => Vec::new(),
+            };
+
+            for response in responses {
+                connection
+                    .sender
+                    .send(response)
+                    .map_err(|_| Error::Disconnected)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            CodeActionRequest::METHOD => {
+                match serde_json::from_value::<CodeActionParams>(request.params) {
+                    Ok(params) => Response::new_ok(request.id, self.code_actions(&params)),
+                    Err(error) => Response::new_err(
+                        request.id,
+                        ErrorCode::InvalidParams as i32,
+                        error.to_string(),
+                    ),
+                }
+            }
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unknown method `{method}`"),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Vec<Message> {
+        let Notification { method, params } = notification;
+
+        let result = match method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                serde_json::from_value(params).map(|params: DidOpenTextDocumentParams| {
+                    self.update(&params.text_document.uri, params.text_document.text)
+                })
+            }
+            DidChangeTextDocument::METHOD => {
+                serde_json::from_value(params).map(|mut params: DidChangeTextDocumentParams| {
+                    match params.content_changes.pop() {
+                        Some(change) => self.update(&params.text_document.uri, change.text),
+                        None => Vec::new(),
+                    }
+                })
+            }
+            DidCloseTextDocument::METHOD => serde_json::from_value(params)
+                .map(|params: DidCloseTextDocumentParams| self.close(&params.text_document.uri)),
+            DidSaveTextDocument::METHOD => {
+                serde_json::from_value(params).map(|params: DidSaveTextDocumentParams| {
+                    if self.is_watched(&params.text_document.uri) {
+                        self.reload_config()
+                    } else {
+                        Vec::new()
+                    }
+                })
+            }
+            DidChangeWatchedFiles::METHOD => {
+                serde_json::from_value(params).map(|params: DidChangeWatchedFilesParams| {
+                    if params
+                        .changes
+                        .iter()
+                        .any(|change| self.is_watched(&change.uri))
+                    {
+                        self.reload_config()
+                    } else {
+                        Vec::new()
+                    }
+                })
+            }
+            _ => Ok(Vec::new()),
+        };
+
+        result.unwrap_or_else(|error| {
+            warn!("Invalid params of `{method}`: {error}");
+            Vec::new()
+        })
+    }
+
+    /// Lint new text of document and publish diagnostics
+    #[instrument(skip(self, text))]
+    fn update(&mut self, uri: &Url, text: String) -> Vec<Message> {
+        let Some(path) = note_path(uri) else {
+            return Vec::new();
+        };
+
+        // Unsaved new note isn't ignored until it is written to disk
+        let (violations, mut messages) = if self.discovery.contains(&path) || !path.exists() {
+            self.lint(&path, &text)
+        } else {
+            debug!("Note `{}` is ignored", path.display());
+            (Vec::new(), Vec::new())
+        };
+        self.documents
+            .insert(uri.clone(), Document { text, violations });
+
+        messages.push(self.publish(uri));
+        messages
+    }
+
+    /// Returns violations and log messages about failed rules
+    fn lint(&self, path: &Path, text: &str) -> (Vec<RuleViolation>, Vec<Message>) {
+        let note = match parse_note(text, path) {
+            Ok(note) => note,
+            Err(error) => {
+                let message = format!("Failed parse `{}`: {error}", path.display());
+                return (Vec::new(), vec![log_message(MessageType::ERROR, message)]);
+            }
+        };
+
+        let content = self.content.clone().with_source(path, text);
+        let check = Linter::new(&self.config, &self.root).check(&content, &note);
+
+        let messages = check
+            .failures
+            .iter()
+            .map(|failure| {
+                log_message(
+                    MessageType::ERROR,
+                    format!(
+                        "Rule '{}' failed on `{}`: {}",
+                        failure.rule,
+                        path.display(),
+                        failure.error
+                    ),
+                )
+            })
+            .collect();
+
+        (check.violations, messages)
+    }
+
+    fn publish(&self, uri: &Url) -> Message {
+        let diagnostics = self
+            .documents
+            .get(uri)
+            .map(|document| {
+                let index = LineIndex::new(&document.text);
+                document
+                    .violations
+                    .iter()
+                    .map(|violation| convert::diagnostic(&index, violation))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        notification(
+            PublishDiagnostics::METHOD,
+            PublishDiagnosticsParams {
+                uri: uri.clone(),
+                diagnostics,
+                version: None,
+            },
+        )
+    }
+
+    fn close(&mut self, uri: &Url) -> Vec<Message> {
+        self.documents.remove(uri);
+        vec![self.publish(uri)]
+    }
+
+    /// Load config again and lint all opened documents
+    #[instrument(skip(self))]
+    fn reload_config(&mut self) -> Vec<Message> {
+        let reloaded = load_config(self.config_path())
+            .map_err(|error| error.to_string())
+            .and_then(|config| {
+                let discovery = discovery(&self.root, &config, self.no_ignore)
+                    .map_err(|error| error.to_string())?;
+                Ok((config, discovery))
+            });
+
+        match reloaded {
+            Ok((config, discovery)) => {
+                self.content = Content::from_files(&self.root, &discovery.notes());
+                self.config = config;
+                self.discovery = discovery;
+            }
+            Err(error) => {
+                return vec![show_message(
+                    MessageType::ERROR,
+                    format!("Failed reload config: {error}"),
+                )];
+            }
+        }
+
+        let documents: Vec<_> = self
+            .documents
+            .iter()
+            .map(|(uri, document)| (uri.clone(), document.text.clone()))
+            .collect();
+
+        documents
+            .into_iter()
+            .flat_map(|(uri, text)| self.update(&uri, text))
+            .collect()
+    }
+
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+
+        let index = LineIndex::new(&document.text);
+        let mut actions = Vec::new();
+
+        for violation in &document.violations {
+            let Some(fix) = violation.violation.fix() else {
+                continue;
+            };
+
+            let diagnostic = convert::diagnostic(&index, violation);
+
+            if !intersects(&diagnostic.range, &params.range) {
+                continue;
+            }
+
+            let unsafe_note = match fix.applicability() {
+                Applicability::Safe => "",
+                Applicability::Unsafe => " (unsafe)",
+            };
+
+            actions.push(
+                CodeAction {
+                    title: format!(
+                        "Fix {}: {}{unsafe_note}",
+                        violation.rule,
+                        violation.violation.message()
+                    ),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic]),
+                    edit: Some(workspace_edit(uri, convert::text_edits(&index, fix))),
+                    is_preferred: Some(fix.applicability() == Applicability::Safe),
+                    ..CodeAction::default()
+                }
+                .into(),
+            );
+        }
+
+        let safe_fixes = document
+            .violations
+            .iter()
+            .filter_map(|violation| violation.violation.fix())
+            .filter(|fix| fix.applicability() == Applicability::Safe);
+
+        let (fixed, applied) = apply_fixes(&document.text, safe_fixes);
+
+        if applied > 0 {
+            let edit = TextEdit::new(convert::range(&index, &(0..document.text.len())), fixed);
+
+            actions.push(
+                CodeAction {
+                    title: "Fix all safe problems".to_string(),
+                    kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+                    edit: Some(workspace_edit(uri, vec![edit])),
+                    ..CodeAction::default()
+                }
+                .into(),
+            );
+        }
+
+        actions
+    }
+}
+
+/// Path of markdown note. Other documents are not linted
+fn note_path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+
+    path.extension()
+        .is_some_and(|extension| extension == "md")
+        .then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::config;
+    use lsp_types::{
+        CodeActionContext, DidChangeWatchedFilesClientCapabilities, FileChangeType, FileEvent,
+        NumberOrString, PartialResultParams, Position, TextDocumentIdentifier, TextDocumentItem,
+        WorkDoneProgressParams, WorkspaceClientCapabilities, notification::Exit, request::Shutdown,
+    };
+    use obsidian_tidy_core::discovery::IGNORE_FILE;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn receive_notification(client: &Connection) -> Notification {
+        match client.receiver.recv().unwrap() {
+            Message::Notification(notification) => notification,
+            message => unreachable!("Expected notification, got {message:?}"),
+        }
+    }
+
+    fn receive_response(client: &Connection) -> Response {
+        match client.receiver.recv().unwrap() {
+            Message::Response(response) => response,
+            message => unreachable!("Expected response, got {message:?}"),
+        }
+    }
+
+    #[test]
+    fn end_to_end() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().to_path_buf();
+        let uri = Url::from_file_path(dir.path().join("note.md")).unwrap();
+
+        let (server, client) = Connection::memory();
+        let server =
+            std::thread::spawn(move || Server::new(root, config(), false).unwrap().run(&server));
+
+        client
+            .sender
+            .send(notification(
+                DidOpenTextDocument::METHOD,
+                DidOpenTextDocumentParams {
+                    text_document: TextDocumentItem::new(
+                        uri.clone(),
+                        "markdown".to_string(),
+                        1,
+                        "😀  text".to_string(),
+                    ),
+                },
+            ))
+            .unwrap();
+
+        let published = receive_notification(&client);
+        assert_eq!(published.method, PublishDiagnostics::METHOD);
+
+        let published: PublishDiagnosticsParams = serde_json::from_value(published.params).unwrap();
+        assert_eq!(published.uri, uri);
+        assert_eq!(published.diagnostics.len(), 1);
+        assert_eq!(
+            published.diagnostics[0].range,
+            Range::new(Position::new(0, 2), Position::new(0, 4))
+        );
+        assert_eq!(
+            published.diagnostics[0].code,
+            Some(NumberOrString::String("double-space".to_string()))
+        );
+
+        client
+            .sender
+            .send(
+                Request::new(
+                    RequestId::from(1),
+                    CodeActionRequest::METHOD.to_string(),
+                    CodeActionParams {
+                        text_document: TextDocumentIdentifier::new(uri.clone()),
+                        range: Range::new(Position::new(0, 3), Position::new(0, 3)),
+                        context: CodeActionContext::default(),
+                        work_done_progress_params: WorkDoneProgressParams::default(),
+                        partial_result_params: PartialResultParams::default(),
+                    },
+                )
+                .into(),
+            )
+            .unwrap();
+
+        let actions: Vec<CodeActionOrCommand> =
+            serde_json::from_value(receive_response(&client).result.unwrap()).unwrap();
+        assert_eq!(actions.len(), 2);
+
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            unreachable!("Expected code action");
+        };
+        let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+        assert_eq!(edits[0].new_text, " ");
+
+        client
+            .sender
+            .send(Request::new(RequestId::from(2), Shutdown::METHOD.to_string(), ()).into())
+            .unwrap();
+        receive_response(&client);
+
+        client.sender.send(notification(Exit::METHOD, ())).unwrap();
+        server.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn close_clears_diagnostics() {
+        let dir = TempDir::new().unwrap();
+        let uri = Url::from_file_path(dir.path().join("note.md")).unwrap();
+        let mut server = Server::new(dir.path(), config(), false).unwrap();
+
+        server.update(&uri, "a  b".to_string());
+        let messages = server.close(&uri);
+
+        let Message::Notification(published) = &messages[0] else {
+            unreachable!("Expected notification");
+        };
+        let published: PublishDiagnosticsParams =
+            serde_json::from_value(published.params.clone()).unwrap();
+
+        assert!(published.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn ignore_not_notes() {
+        let dir = TempDir::new().unwrap();
+        let uri = Url::from_file_path(dir.path().join("image.png")).unwrap();
+        let mut server = Server::new(dir.path(), config(), false).unwrap();
+
+        assert!(server.update(&uri, "a  b".to_string()).is_empty());
+    }
+
+    #[test]
+    fn ignored_notes() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("drafts")).unwrap();
+        fs::write(dir.path().join("drafts/note.md"), "a  b").unwrap();
+        fs::write(dir.path().join(IGNORE_FILE), "drafts/\n").unwrap();
+
+        let uri = Url::from_file_path(dir.path().join("drafts/note.md")).unwrap();
+        let mut server = Server::new(dir.path(), config(), false).unwrap();
+        server.update(&uri, "a  b".to_string());
+        assert!(server.documents[&uri].violations.is_empty());
+
+        let mut server = Server::new(dir.path(), config(), true).unwrap();
+        server.update(&uri, "a  b".to_string());
+        assert_eq!(server.documents[&uri].violations.len(), 1);
+    }
+
+    #[test]
+    fn watchers() {
+        assert!(register_watchers(&ClientCapabilities::default()).is_none());
+
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                did_change_watched_files: Some(DidChangeWatchedFilesClientCapabilities {
+                    dynamic_registration: Some(true),
+                    relative_pattern_support: None,
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+
+        let Some(Message::Request(request)) = register_watchers(&capabilities) else {
+            unreachable!("Expected request");
+        };
+        assert_eq!(request.method, RegisterCapability::METHOD);
+
+        let params: RegistrationParams = serde_json::from_value(request.params).unwrap();
+        let options: DidChangeWatchedFilesRegistrationOptions =
+            serde_json::from_value(params.registrations[0].register_options.clone().unwrap())
+                .unwrap();
+        let globs: Vec<_> = options
+            .watchers
+            .into_iter()
+            .map(|watcher| watcher.glob_pattern)
+            .collect();
+
+        assert!(globs.contains(&GlobPattern::String("**/.obsidian-tidy.toml".to_string())));
+        assert!(globs.contains(&GlobPattern::String(format!("**/{IGNORE_FILE}"))));
+    }
+
+    #[test]
+    fn reload_ignore_file() {
+        let dir = TempDir::new().unwrap();
+        let note = dir.path().join("note.md");
+        fs::write(&note, "text").unwrap();
+        fs::write(dir.path().join(crate::CONFIG_FILE), "").unwrap();
+
+        let mut server = Server::new(dir.path(), config(), false).unwrap();
+        assert!(server.discovery.contains(&note));
+
+        fs::write(dir.path().join(IGNORE_FILE), "note.md\n").unwrap();
+        let change = FileEvent::new(
+            Url::from_file_path(dir.path().join(IGNORE_FILE)).unwrap(),
+            FileChangeType::CREATED,
+        );
+        server.handle_notification(Notification::new(
+            DidChangeWatchedFiles::METHOD.to_string(),
+            DidChangeWatchedFilesParams {
+                changes: vec![change],
+            },
+        ));
+
+        assert!(!server.discovery.contains(&note));
+    }
+}
@@ -0,0 +1,448 @@
+//! Suppress violations by comments in note and by frontmatter
+//!
+//! - `<!-- obsidian-tidy-disable rule-a rule-b -->` until `<!-- obsidian-tidy-enable -->` or the end of note
+//! - `<!-- obsidian-tidy-disable-next-line rule-a -->`
+//! - `<!-- obsidian-tidy-disable-file rule-a -->`
+//! - frontmatter `obsidian-tidy: { disable: [rule-a] }`
+//!
+//! Comments without names of rules suppress all rules.
+//! `<!-- obsidian-tidy-enable rule-a -->` enables `rule-a` again, other rules of its regions stay disabled,
+//! also after comment without names of rules.
+//! Without names of rules it closes all regions. Comments in code blocks are ignored
+
+use crate::linter::{NoteCheck, RuleViolation};
+use obsidian_tidy_core::{
+    code_blocks,
+    frontmatter::Frontmatter,
+    rule::{Category, Severity, Violation},
+};
+use serde::Deserialize;
+use std::ops::Range;
+use tracing::{debug, instrument};
+
+/// Name of pseudo rule which reports suppressions without suppressed violations
+pub const UNUSED_SUPPRESSION: &str = "unused-suppression";
+
+const PREFIX: &str = "obsidian-tidy-";
+
+/// Key of frontmatter with settings of obsidian-tidy
+const FRONTMATTER_KEY: &str = "obsidian-tidy";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Region,
+    NextLine,
+    File,
+    Frontmatter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    pub kind: Kind,
+
+    /// Names of suppressed rules. Empty means all rules
+    pub rules: Vec<String>,
+
+    /// Names of rules enabled again in suppression of all rules
+    pub excluded: Vec<String>,
+
+    /// Byte range of comment or frontmatter
+    pub location: Range<usize>,
+
+    /// Byte range where violations are suppressed
+    pub scope: Range<usize>,
+}
+
+impl Suppression {
+    fn suppresses(&self, violation: &RuleViolation) -> bool {
+        let start = violation.violation.location().start;
+
+        self.is_for(&violation.rule) && self.scope.start <= start && start <= self.scope.end
+    }
+
+    fn is_for(&self, rule: &str) -> bool {
+        if self.rules.is_empty() {
+            !self.excluded.iter().any(|name| name == rule)
+        } else {
+            self.rules.iter().any(|name| name == rule)
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Properties {
+    #[serde(rename = "obsidian-tidy", default)]
+    tidy: Option<TidyProperties>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TidyProperties {
+    #[serde(default)]
+    disable: Vec<String>,
+}
+
+fn from_frontmatter(source: &str) -> Option<Suppression> {
+    let frontmatter = Frontmatter::find(source)?;
+
+    if !frontmatter.yaml().contains(FRONTMATTER_KEY) {
+        return None;
+    }
+
+    let properties: Properties = frontmatter
+        .parse()
+        .inspect_err(|error| debug!("Failed parse frontmatter: {error}"))
+        .ok()?;
+
+    let rules = properties.tidy?.disable;
+
+    // Empty list disables nothing
+    (!rules.is_empty()).then(|| Suppression {
+        kind: Kind::Frontmatter,
+        rules,
+        excluded: Vec::new(),
+        location: frontmatter.location().clone(),
+        scope: 0..source.len(),
+    })
+}
+
+/// Byte range of line after `offset`
+fn next_line(source: &str, offset: usize) -> Range<usize> {
+    let start = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index + 1);
+    let end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |index| start + index);
+
+    start..end
+}
+
+/// Find all suppressions in source of note
+#[must_use]
+pub fn parse(source: &str) -> Vec<Suppression> {
+    let mut suppressions: Vec<_> = from_frontmatter(source).into_iter().collect();
+    let mut regions: Vec<Suppression> = Vec::new();
+    let mut position = 0;
+    let blocks = code_blocks::find(source);
+
+    for (start, _) in source.match_indices("<!--") {
+        if start < position || blocks.iter().any(|block| block.contains(&start)) {
+            continue;
+        }
+
+        let Some(length) = source[start..].find("-->") else {
+            break;
+        };
+
+        let end = start + length + "-->".len();
+        position = end;
+
+        let Some(directive) = source[start + "<!--".len()..start + length]
+            .trim()
+            .strip_prefix(PREFIX)
+        else {
+            continue;
+        };
+
+        let mut words = directive.split_whitespace();
+        let kind = words.next().unwrap_or_default();
+        let rules: Vec<String> = words
+            .map(|word| word.trim_matches(','))
+            .filter(|word| !word.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        let (kind, scope) = match kind {
+            "disable" => (Kind::Region, end..source.len()),
+            "disable-next-line" => (Kind::NextLine, next_line(source, end)),
+            "disable-file" => (Kind::File, 0..source.len()),
+            "enable" => {
+                let (closed, open): (Vec<_>, Vec<_>) = regions.into_iter().partition(|region| {
+                    rules.is_empty() || rules.iter().any(|rule| region.is_for(rule))
+                });
+
+                regions = open;
+
+                for region in closed {
+                    // Region goes on for rules which are not enabled
+                    let rest = if rules.is_empty() {
+                        None
+                    } else if region.rules.is_empty() {
+                        let excluded = region.excluded.iter().chain(&rules).cloned().collect();
+                        Some((Vec::new(), excluded))
+                    } else {
+                        let rest: Vec<String> = region
+                            .rules
+                            .iter()
+                            .filter(|rule| !rules.contains(rule))
+                            .cloned()
+                            .collect();
+
+                        (!rest.is_empty()).then(|| (rest, Vec::new()))
+                    };
+
+                    if let Some((rules, excluded)) = rest {
+                        regions.push(Suppression {
+                            rules,
+                            excluded,
+                            scope: end..region.scope.end,
+                            ..region.clone()
+                        });
+                    }
+
+                    suppressions.push(Suppression {
+                        scope: region.scope.start..start,
+                        ..region
+                    });
+                }
+
+                continue;
+            }
+            other => {
+                debug!("Unknown directive `{PREFIX}{other}`");
+                continue;
+            }
+        };
+
+        let suppression = Suppression {
+            kind,
+            rules,
+            excluded: Vec::new(),
+            location: start..end,
+            scope,
+        };
+
+        if kind == Kind::Region {
+            regions.push(suppression);
+        } else {
+            suppressions.push(suppression);
+        }
+    }
+
+    suppressions.extend(regions);
+    suppressions.sort_by_key(|suppression| suppression.location.start);
+    suppressions
+}
+
+fn unused_violation(suppression: &Suppression) -> Option<RuleViolation> {
+    let message = if suppression.rules.is_empty() {
+        "Unused suppression".to_string()
+    } else {
+        let rules: Vec<_> = suppression
+            .rules
+            .iter()
+            .map(|rule| format!("`{rule}`"))
+            .collect();
+
+        format!("Unused suppression of {}", rules.join(", "))
+    };
+
+    let violation = Violation::new(message, suppression.location.clone()).ok()?;
+
+    Some(RuleViolation {
+        rule: UNUSED_SUPPRESSION.to_string(),
+        category: Category::Other,
+        severity: Severity::Warning,
+        violation,
+    })
+}
+
+/// Move suppressed violations of check to [`NoteCheck::suppressed`]
+/// and report unused suppressions.
+/// Suppressions only of rules which are not in `checked` are never unused:
+/// these rules may be disabled for this note
+#[instrument(skip_all)]
+pub fn apply(source: &str, check: &mut NoteCheck, checked: &[&str]) {
+    let suppressions = parse(source);
+
+    if suppressions.is_empty() {
+        return;
+    }
+
+    let mut used = vec![false; suppressions.len()];
+
+    let (suppressed, violations): (Vec<_>, Vec<_>) = std::mem::take(&mut check.violations)
+        .into_iter()
+        .partition(|violation| {
+            let mut is_suppressed = false;
+
+            for (suppression, used) in suppressions.iter().zip(used.iter_mut()) {
+                if suppression.suppresses(violation) {
+                    *used = true;
+                    is_suppressed = true;
+                }
+            }
+
+            is_suppressed
+        });
+
+    check.violations = violations;
+    check.suppressed.extend(suppressed);
+
+    // Region split by `enable` is several suppressions of one comment.
+    // Comment is unused only if all of them are unused
+    let mut reported: Vec<&Range<usize>> = Vec::new();
+
+    for suppression in &suppressions {
+        let is_used = suppressions
+            .iter()
+            .zip(&used)
+            .any(|(other, used)| *used && other.location == suppression.location);
+
+        if is_used
+            || reported.contains(&&suppression.location)
+            || !checked.iter().any(|rule| suppression.is_for(rule))
+        {
+            continue;
+        }
+
+        reported.push(&suppression.location);
+        check.violations.extend(unused_violation(suppression));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(rule: &str, location: Range<usize>) -> RuleViolation {
+        RuleViolation {
+            rule: rule.to_string(),
+            category: Category::Other,
+            severity: Severity::Warning,
+            violation: Violation::new("Problem", location).unwrap(),
+        }
+    }
+
+    #[test]
+    fn parse_comments() {
+        let source = "<!-- obsidian-tidy-disable a b -->\n\
+                      text\n\
+                      <!-- obsidian-tidy-enable b -->\n\
+                      <!-- obsidian-tidy-disable-next-line -->\n\
+                      line\n\
+                      <!-- obsidian-tidy-disable-file c -->\n\
+                      <!-- other comment -->\n";
+
+        let suppressions = parse(source);
+        let kinds: Vec<_> = suppressions.iter().map(|s| s.kind).collect();
+
+        assert_eq!(
+            kinds,
+            [Kind::Region, Kind::Region, Kind::NextLine, Kind::File]
+        );
+        assert_eq!(suppressions[0].rules, ["a", "b"]);
+        assert_eq!(&source[suppressions[0].scope.clone()], "\ntext\n");
+        assert_eq!(suppressions[1].rules, ["a"]);
+        assert_eq!(suppressions[1].location, suppressions[0].location);
+        assert!(source[suppressions[1].scope.clone()].ends_with("<!-- other comment -->\n"));
+        assert!(suppressions[2].rules.is_empty());
+        assert_eq!(&source[suppressions[2].scope.clone()], "line");
+        assert_eq!(suppressions[3].scope, 0..source.len());
+    }
+
+    #[test]
+    fn enable_all() {
+        let source = "<!-- obsidian-tidy-disable a b -->\ntext\n<!-- obsidian-tidy-enable -->\n";
+        let suppressions = parse(source);
+
+        assert_eq!(suppressions.len(), 1);
+        assert_eq!(&source[suppressions[0].scope.clone()], "\ntext\n");
+    }
+
+    #[test]
+    fn enable_after_disable_all() {
+        let source = "<!-- obsidian-tidy-disable -->\nbad\n<!-- obsidian-tidy-enable a -->\nbad\n";
+        let mut check = NoteCheck {
+            violations: vec![
+                violation("a", 31..34),
+                violation("a", 67..70),
+                violation("b", 67..70),
+            ],
+            ..NoteCheck::default()
+        };
+
+        apply(source, &mut check, &["a", "b"]);
+
+        assert_eq!(
+            check.suppressed,
+            [violation("a", 31..34), violation("b", 67..70)]
+        );
+        assert_eq!(check.violations, [violation("a", 67..70)]);
+
+        let suppressions = parse(source);
+        assert_eq!(suppressions.len(), 2);
+        assert_eq!(&source[suppressions[0].scope.clone()], "\nbad\n");
+        assert!(suppressions[1].rules.is_empty());
+        assert_eq!(suppressions[1].excluded, ["a"]);
+    }
+
+    #[test]
+    fn code_block() {
+        let source = "```markdown\n<!-- obsidian-tidy-disable-file a -->\n```\n\
+                      ~~~\n<!-- obsidian-tidy-disable-file b -->\n```\n~~~\n\
+                      <!-- obsidian-tidy-disable-file c -->\n";
+        let suppressions = parse(source);
+
+        assert_eq!(suppressions.len(), 1);
+        assert_eq!(suppressions[0].rules, ["c"]);
+    }
+
+    #[test]
+    fn split_region_used() {
+        let source =
+            "<!-- obsidian-tidy-disable a b -->\nbad\n<!-- obsidian-tidy-enable b -->\nbad\n";
+        let mut check = NoteCheck {
+            violations: vec![violation("b", 35..38)],
+            ..NoteCheck::default()
+        };
+
+        apply(source, &mut check, &["a", "b"]);
+
+        assert_eq!(check.suppressed, [violation("b", 35..38)]);
+        assert!(check.violations.is_empty());
+    }
+
+    #[test]
+    fn parse_frontmatter() {
+        let source = "---\nobsidian-tidy:\n  disable: [a]\n---\ntext";
+        let suppressions = parse(source);
+
+        assert_eq!(suppressions.len(), 1);
+        assert_eq!(suppressions[0].kind, Kind::Frontmatter);
+        assert_eq!(suppressions[0].rules, ["a"]);
+        assert_eq!(suppressions[0].location, 0..38);
+
+        assert!(parse("---\ntitle: text\n---\n").is_empty());
+    }
+
+    #[test]
+    fn suppress() {
+        let source = "<!-- obsidian-tidy-disable-next-line a -->\nbad\nbad\n";
+        let mut check = NoteCheck {
+            violations: vec![violation("a", 43..46), violation("a", 47..50)],
+            ..NoteCheck::default()
+        };
+
+        apply(source, &mut check, &["a"]);
+
+        assert_eq!(check.suppressed, [violation("a", 43..46)]);
+        assert_eq!(check.violations, [violation("a", 47..50)]);
+    }
+
+    #[test]
+    fn unused_suppression() {
+        let source = "<!-- obsidian-tidy-disable-file a -->\n<!-- obsidian-tidy-disable-file b -->";
+        let mut check = NoteCheck::default();
+
+        apply(source, &mut check, &["a"]);
+
+        assert_eq!(check.violations.len(), 1);
+        assert_eq!(check.violations[0].rule, UNUSED_SUPPRESSION);
+        assert_eq!(check.violations[0].violation.location(), &(0..37));
+        assert_eq!(
+            check.violations[0].violation.message(),
+            "Unused suppression of `a`"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Module for testing
+
+use obsidian_tidy_config::{Config, builder::ConfigBuilder};
+use obsidian_tidy_core::{
+    Note,
+    rule::{
+        Applicability, Category, Content, Edit, Fix, Rule, Rules, SharedErrorRule, ToggleableRule,
+        Violation,
+    },
+};
+use std::convert::Infallible;
+
+/// Replace the first double space by one space
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DoubleSpace;
+
+impl Rule for DoubleSpace {
+    type Error = Infallible;
+
+    fn name(&self) -> &'static str {
+        "double-space"
+    }
+
+    fn description(&self) -> &'static str {
+        "Double spaces"
+    }
+
+    fn category(&self) -> Category {
+        Category::Spacing
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        Some(Applicability::Safe)
+    }
+
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        let source = content.source(note).unwrap_or_default();
+
+        Ok(source
+            .find("  ")
+            .map(|start| {
+                Violation::new("Double space", start..start + 2)
+                    .unwrap()
+                    .with_fix(Fix::safe([Edit::replace(start..start + 2, " ")]))
+            })
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Config with enabled [`DoubleSpace`]
+pub(crate) fn config() -> Config {
+    let rule = ToggleableRule::new(SharedErrorRule::new(DoubleSpace), true);
+
+    ConfigBuilder::default()
+        .rules(Rules::new(vec![rule]).unwrap())
+        .build()
+}
@@ -1,30 +1,63 @@
 //! Builder for config
 
-use super::Config;
+use super::{Config, General, custom::CustomRule};
 use obsidian_tidy_core::rule::{Rules, SharedErrorRule};
+use std::{collections::BTreeMap, path::PathBuf};
 
 #[derive(Debug)]
 pub struct ConfigBuilder {
+    general: General,
     rules: Rules<SharedErrorRule>,
+    custom: BTreeMap<String, CustomRule>,
+    overrides: Vec<(PathBuf, Config)>,
 }
 
 impl Default for ConfigBuilder {
     fn default() -> Self {
         Self {
+            general: General::default(),
             rules: Rules::new(Vec::new()).unwrap(),
+            custom: BTreeMap::new(),
+            overrides: Vec::new(),
         }
     }
 }
 
 impl ConfigBuilder {
+    #[must_use]
+    pub fn general(mut self, general: General) -> Self {
+        self.general = general;
+        self
+    }
+
     #[must_use]
     pub fn rules(mut self, rules: Rules<SharedErrorRule>) -> Self {
         self.rules = rules;
         self
     }
 
+    /// Custom rules by names. Their rules must be added to [`rules`](Self::rules)
+    #[must_use]
+    pub fn custom(mut self, custom: BTreeMap<String, CustomRule>) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    /// Configs of subfolders relative to vault
+    #[must_use]
+    pub fn overrides(mut self, mut overrides: Vec<(PathBuf, Config)>) -> Self {
+        overrides.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.overrides = overrides;
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> Config {
-        Config { rules: self.rules }
+        Config {
+            general: self.general,
+            rules: self.rules,
+            custom: self.custom,
+            overrides: self.overrides,
+        }
     }
 }
@@ -0,0 +1,150 @@
+//! Regex rules declared in `[custom.<name>]` of config
+
+use obsidian_tidy_core::rule::{
+    Category, PathFilter, Severity, SharedErrorRule, ToggleableRule,
+    path_filter::Error as PathFilterError,
+};
+use obsidian_tidy_rules::pattern::PatternRule;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Top-level key of custom rules
+pub const CUSTOM: &str = "custom";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid pattern of custom rule `{name}`: {source}")]
+    Pattern { name: String, source: regex::Error },
+
+    #[error("Custom rule `{name}`: {source}")]
+    Paths {
+        name: String,
+        source: PathFilterError,
+    },
+}
+
+const fn enabled() -> bool {
+    true
+}
+
+const fn other() -> Category {
+    Category::Other
+}
+
+/// Rule which reports every match of regex
+///
+/// # Example
+/// ```toml
+/// [custom.no-http]
+/// pattern = 'http://(\S+)'
+/// message = "Use HTTPS links"
+/// replacement = "https://$1"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CustomRule {
+    /// Regex of forbidden text
+    pub pattern: String,
+
+    /// Message of violation
+    pub message: String,
+
+    /// Replacement of match for fix, `$1` and `$name` are expanded to groups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+
+    /// Description of rule. Defaults to `message`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(default = "other")]
+    pub category: Category,
+
+    #[serde(default = "enabled")]
+    pub enable: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+
+    /// Globs of notes which are checked. Empty means all notes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Globs of notes which are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// Tags of notes which are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_tags: Vec<String>,
+}
+
+impl CustomRule {
+    /// Rule with `name` for [`Rules`](obsidian_tidy_core::rule::Rules)
+    pub fn rule(&self, name: &str) -> Result<ToggleableRule<SharedErrorRule>, Error> {
+        let mut rule = PatternRule::new(name, &self.pattern, &self.message)
+            .map_err(|source| Error::Pattern {
+                name: name.to_string(),
+                source,
+            })?
+            .with_category(self.category.clone());
+
+        if let Some(description) = &self.description {
+            rule = rule.with_description(description);
+        }
+
+        if let Some(replacement) = &self.replacement {
+            rule = rule.with_replacement(replacement);
+        }
+
+        let paths =
+            PathFilter::new(&self.include, &self.exclude).map_err(|source| Error::Paths {
+                name: name.to_string(),
+                source,
+            })?;
+
+        Ok(ToggleableRule::new(SharedErrorRule::new(rule), self.enable)
+            .with_severity(self.severity)
+            .with_paths(paths)
+            .with_ignore_tags(self.ignore_tags.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_core::rule::{Applicability, Rule};
+
+    #[test]
+    fn rule() {
+        let custom: CustomRule = toml::from_str(
+            r#"
+            pattern = 'http://(\S+)'
+            message = "Use HTTPS links"
+            replacement = "https://$1"
+            category = "content"
+            exclude = ["daily/**"]
+            "#,
+        )
+        .unwrap();
+
+        let rule = custom.rule("no-http").unwrap();
+
+        assert_eq!(rule.name(), "no-http");
+        assert_eq!(rule.description(), "Use HTTPS links");
+        assert_eq!(rule.category(), Category::Content);
+        assert_eq!(rule.fixable(), Some(Applicability::Safe));
+        assert!(rule.is_enabled());
+        assert_eq!(rule.paths().exclude(), ["daily/**"]);
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let custom: CustomRule = toml::from_str("pattern = '('\nmessage = \"Broken\"\n").unwrap();
+
+        assert!(matches!(
+            custom.rule("broken"),
+            Err(Error::Pattern { name, .. }) if name == "broken"
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Errors for Config
 
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -10,6 +11,30 @@ pub enum Error {
     #[error("Toml deserialize error `{0}`")]
     Deserialize(#[from] toml::de::Error),
 
+    #[error("Toml parse error `{0}`")]
+    Edit(#[from] toml_edit::TomlError),
+
+    #[error("JSON error `{0}`")]
+    Json(#[from] serde_json::Error),
+
     #[error("Toml serialize error `{0}`")]
     Serialize(#[from] toml::ser::Error),
+
+    #[error("Config extends itself: `{}`", .0.display())]
+    ExtendsCycle(PathBuf),
+
+    #[error("Failed load extended config `{}`: {source}", .path.display())]
+    Extends { path: PathBuf, source: Box<Error> },
+
+    #[error("Unknown preset `{0}`. Use a path to `.toml` file or one of: all, standard, empty")]
+    UnknownPreset(String),
+
+    #[error("`rules` and its categories must be tables")]
+    InvalidRules,
+
+    #[error("`extends` must be a string or an array of strings")]
+    InvalidExtends,
+
+    #[error("Failed load custom rules: {0}")]
+    CustomRules(#[from] obsidian_tidy_rules::Error),
 }
@@ -0,0 +1,41 @@
+//! General options of config
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct General {
+    /// Globs of notes which are never linted. Relative to vault
+    pub exclude: Vec<String>,
+
+    /// Skip notes ignored by `.gitignore`
+    pub respect_gitignore: bool,
+
+    /// Tags of notes which are never linted, like `#draft`
+    pub ignore_tags: Vec<String>,
+}
+
+impl Default for General {
+    fn default() -> Self {
+        Self {
+            exclude: Vec::new(),
+            respect_gitignore: true,
+            ignore_tags: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let general: General =
+            toml::from_str("exclude = [\"templates/**\"]\nignore-tags = [\"#draft\"]").unwrap();
+
+        assert_eq!(general.exclude, ["templates/**"]);
+        assert!(general.respect_gitignore);
+        assert_eq!(general.ignore_tags, ["#draft"]);
+    }
+}
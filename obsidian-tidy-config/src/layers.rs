@@ -0,0 +1,245 @@
+//! Layers of config
+//!
+//! Config of vault is built from layers, where every next layer overrides the previous one:
+//! 1. user-global config in [`global_config_path`]
+//! 2. configs from `extends` of config of vault
+//! 3. config of vault
+//! 4. configs in subfolders of vault. They override config only for notes in their subfolder
+//!
+//! Tables are merged key by key, other values (arrays too) are replaced
+
+use super::{Error, template::Template};
+use clap::ValueEnum;
+use obsidian_tidy_core::directories::directories;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+use tracing::{debug, instrument};
+
+/// Name of user-global config in [`config_dir`](obsidian_tidy_core::directories::Directories::config_dir)
+pub const GLOBAL_CONFIG_FILE: &str = "config.toml";
+
+/// Key with configs which are extended. Path relative to config or name of [`Template`]
+pub const EXTENDS: &str = "extends";
+
+/// Path of user-global config
+#[must_use]
+pub fn global_config_path() -> PathBuf {
+    directories().config_dir().join(GLOBAL_CONFIG_FILE)
+}
+
+/// Merge `overlay` into `base`
+pub fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Rules of template as config
+pub(crate) fn preset(name: &str) -> Result<Table, Error> {
+    let template =
+        Template::from_str(name, true).map_err(|_| Error::UnknownPreset(name.to_string()))?;
+
+    let mut table = Table::new();
+    table.insert("rules".to_string(), Value::try_from(&*template)?);
+
+    Ok(table)
+}
+
+/// Value of `extends` is path to config, if it looks like file
+pub(crate) fn is_path(extends: &str) -> bool {
+    Path::new(extends)
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+}
+
+/// Read config file with configs which it extends
+#[instrument(err)]
+pub fn read(path: &Path) -> Result<Table, Error> {
+    read_with_stack(path, &mut Vec::new())
+}
+
+fn read_with_stack(path: &Path, stack: &mut Vec<PathBuf>) -> Result<Table, Error> {
+    let canonical = fs::canonicalize(path)?;
+
+    if stack.contains(&canonical) {
+        return Err(Error::ExtendsCycle(path.to_path_buf()));
+    }
+
+    let table: Table = toml::from_str(&fs::read_to_string(path)?)?;
+
+    stack.push(canonical);
+    let table = extend(path, table, stack);
+    stack.pop();
+
+    table
+}
+
+/// Merge configs from `extends` of config `table`, which is read from `path`
+pub fn resolve(path: &Path, table: Table) -> Result<Table, Error> {
+    let mut stack = fs::canonicalize(path).into_iter().collect();
+    extend(path, table, &mut stack)
+}
+
+fn extend(path: &Path, mut table: Table, stack: &mut Vec<PathBuf>) -> Result<Table, Error> {
+    let extends = match table.remove(EXTENDS) {
+        None => Vec::new(),
+        Some(Value::String(extends)) => vec![extends],
+        Some(Value::Array(values)) => values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(extends) => Ok(extends),
+                _ => Err(Error::InvalidExtends),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(Error::InvalidExtends),
+    };
+
+    if extends.is_empty() {
+        return Ok(table);
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut base = Table::new();
+
+    for extends in extends {
+        debug!("Extend `{extends}`");
+
+        let layer = if is_path(&extends) {
+            let path = dir.join(&extends);
+            read_with_stack(&path, stack).map_err(|error| Error::Extends {
+                path,
+                source: Box::new(error),
+            })?
+        } else {
+            preset(&extends)?
+        };
+
+        merge(&mut base, layer);
+    }
+
+    merge(&mut base, table);
+    Ok(base)
+}
+
+/// Folders below `root` with config file `name`. Hidden folders are skipped.
+/// Parent folders are before their subfolders
+#[must_use]
+pub fn nested(root: &Path, name: &str) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+
+            if !hidden && entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                if path.join(name).is_file() {
+                    found.push(path.clone());
+                }
+
+                dirs.push(path);
+            }
+        }
+    }
+
+    found.sort();
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn table(toml: &str) -> Table {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn merge_tables() {
+        let mut base = table("a = 1\n[rules.content.x]\nenable = true\nexclude = [\"a\"]\n");
+        merge(
+            &mut base,
+            table("b = 2\n[rules.content.x]\nexclude = [\"b\"]\n"),
+        );
+
+        assert_eq!(
+            base,
+            table("a = 1\nb = 2\n[rules.content.x]\nenable = true\nexclude = [\"b\"]\n")
+        );
+    }
+
+    #[test]
+    fn extends() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("shared")).unwrap();
+        fs::write(
+            dir.path().join("shared/base.toml"),
+            "extends = \"empty\"\n[general]\nignore-tags = [\"draft\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            "extends = \"shared/base.toml\"\n[rules.content.empty-content]\nenable = true\n",
+        )
+        .unwrap();
+
+        let config = read(&dir.path().join("config.toml")).unwrap();
+
+        assert!(!config.contains_key(EXTENDS));
+        assert_eq!(config["general"]["ignore-tags"][0].as_str(), Some("draft"));
+        assert_eq!(
+            config["rules"]["content"]["empty-content"]["enable"].as_bool(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn extends_cycle() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.toml"), "extends = \"b.toml\"").unwrap();
+        fs::write(dir.path().join("b.toml"), "extends = \"a.toml\"").unwrap();
+
+        let error = read(&dir.path().join("a.toml")).unwrap_err();
+        assert!(error.to_string().contains("extends itself"));
+    }
+
+    #[test]
+    fn unknown_preset() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.toml"), "extends = \"unknown\"").unwrap();
+
+        assert!(matches!(
+            read(&dir.path().join("a.toml")),
+            Err(Error::UnknownPreset(name)) if name == "unknown"
+        ));
+    }
+
+    #[test]
+    fn nested_configs() {
+        let dir = TempDir::new().unwrap();
+
+        for path in ["daily/sub", "projects", ".obsidian"] {
+            fs::create_dir_all(dir.path().join(path)).unwrap();
+            fs::write(dir.path().join(path).join("c.toml"), "").unwrap();
+        }
+
+        assert_eq!(
+            nested(dir.path(), "c.toml"),
+            [dir.path().join("daily/sub"), dir.path().join("projects")]
+        );
+    }
+}
@@ -1,27 +1,145 @@
 //! Crate for configuration
 
 pub mod builder;
+pub mod custom;
 pub mod error;
+pub mod general;
+pub mod layers;
 pub mod loader;
+pub mod migrate;
 pub mod saver;
+pub mod schema;
 pub mod template;
+pub mod update;
+pub mod validate;
 
-use obsidian_tidy_core::rule::{Rules, SharedErrorRule};
-use serde::Serialize;
-use thiserror::Error;
+use custom::{CUSTOM, CustomRule};
+use obsidian_tidy_core::rule::{Rule, Rules, SharedErrorRule};
+use serde::{Serialize, Serializer, ser::Error as _, ser::SerializeStruct};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 pub use error::Error;
+pub use general::General;
 pub use saver::ConfigSaver;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug)]
 pub struct Config {
+    general: General,
+
+    /// Configured rules with custom rules
     rules: Rules<SharedErrorRule>,
+
+    /// Custom rules by names
+    custom: BTreeMap<String, CustomRule>,
+
+    /// Configs of subfolders relative to vault. Parent folders are before their subfolders
+    overrides: Vec<(PathBuf, Config)>,
 }
 
 impl Config {
+    /// Get general options
+    #[must_use]
+    pub const fn general(&self) -> &General {
+        &self.general
+    }
+
     /// Get all rules
     #[must_use]
     pub const fn rules(&self) -> &Rules<SharedErrorRule> {
         &self.rules
     }
+
+    /// Get custom rules by names. They are also in [`rules`](Self::rules)
+    #[must_use]
+    pub const fn custom(&self) -> &BTreeMap<String, CustomRule> {
+        &self.custom
+    }
+
+    /// Configs of subfolders relative to vault
+    #[must_use]
+    pub fn overrides(&self) -> &[(PathBuf, Config)] {
+        &self.overrides
+    }
+
+    /// Config for note with `path` relative to vault
+    #[must_use]
+    pub fn for_note(&self, path: &Path) -> &Config {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|(dir, _)| path.starts_with(dir))
+            .map_or(self, |(_, config)| config)
+    }
+}
+
+impl Serialize for Config {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Custom rules are written to their own section
+        let rules = Rules::new(
+            self.rules
+                .iter()
+                .filter(|rule| !self.custom.contains_key(rule.name()))
+                .cloned()
+                .collect(),
+        )
+        .map_err(S::Error::custom)?;
+
+        let mut config = serializer.serialize_struct("Config", 3)?;
+        config.serialize_field("general", &self.general)?;
+        config.serialize_field("rules", &rules)?;
+
+        if self.custom.is_empty() {
+            config.skip_field(CUSTOM)?;
+        } else {
+            config.serialize_field(CUSTOM, &self.custom)?;
+        }
+
+        config.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use builder::ConfigBuilder;
+
+    #[test]
+    fn for_note() {
+        let general = |tag: &str| General {
+            ignore_tags: vec![tag.to_string()],
+            ..General::default()
+        };
+
+        let config = ConfigBuilder::default()
+            .overrides(vec![
+                (
+                    PathBuf::from("daily"),
+                    ConfigBuilder::default().general(general("a")).build(),
+                ),
+                (
+                    PathBuf::from("daily/sub"),
+                    ConfigBuilder::default().general(general("b")).build(),
+                ),
+            ])
+            .build();
+
+        let tags = |path: &str| {
+            config
+                .for_note(Path::new(path))
+                .general()
+                .ignore_tags
+                .clone()
+        };
+
+        assert!(tags("note.md").is_empty());
+        assert_eq!(tags("daily/note.md"), ["a"]);
+        assert_eq!(tags("daily/sub/note.md"), ["b"]);
+        assert!(tags("daily-other/note.md").is_empty());
+    }
 }
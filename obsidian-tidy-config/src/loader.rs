@@ -1,12 +1,17 @@
 //! Module for load config
 
-use super::Config;
 use super::Error;
-use obsidian_tidy_core::rule::{RulesSeed, SharedErrorRule};
+use super::{Config, General, builder::ConfigBuilder, custom::CustomRule, layers};
+use obsidian_tidy_core::rule::{Rule, Rules, RulesSeed, SharedErrorRule};
 use obsidian_tidy_rules::ALL_RULES;
 use serde::de::DeserializeSeed;
 use serde::{Deserialize, Deserializer};
-use std::io::Read;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    path::{Path, PathBuf},
+};
+use toml::Table;
 use tracing::{debug, instrument};
 
 #[derive(Debug)]
@@ -22,12 +27,12 @@ impl Default for ConfigLoader<'_> {
 
 #[derive(Debug)]
 struct ConfigSeed<'a> {
-    rule_seed: &'a RulesSeed<'a, SharedErrorRule>,
+    available_rules: &'a Vec<SharedErrorRule>,
 }
 
 impl<'a> ConfigSeed<'a> {
-    const fn new(rule_seed: &'a RulesSeed<'a, SharedErrorRule>) -> Self {
-        Self { rule_seed }
+    const fn new(available_rules: &'a Vec<SharedErrorRule>) -> Self {
+        Self { available_rules }
     }
 }
 
@@ -40,14 +45,42 @@ impl<'de> DeserializeSeed<'de> for ConfigSeed<'_> {
     {
         #[derive(Deserialize)]
         struct InnerConfig {
+            #[serde(default)]
+            general: General,
+
+            #[serde(default = "empty_table")]
             rules: toml::Value,
+
+            #[serde(default)]
+            custom: BTreeMap<String, CustomRule>,
+        }
+
+        fn empty_table() -> toml::Value {
+            toml::Value::Table(Table::new())
         }
 
         let inner = InnerConfig::deserialize(deserializer)?;
 
-        Ok(Self::Value {
-            rules: self.rule_seed.clone().deserialize(inner.rules).unwrap(),
-        })
+        let mut rules = RulesSeed::new(self.available_rules)
+            .deserialize(inner.rules)
+            .map_err(serde::de::Error::custom)?
+            .to_vec();
+
+        for (name, custom) in &inner.custom {
+            if self.available_rules.iter().any(|rule| rule.name() == name) {
+                return Err(serde::de::Error::custom(format!(
+                    "Custom rule `{name}` has the same name as another rule"
+                )));
+            }
+
+            rules.push(custom.rule(name).map_err(serde::de::Error::custom)?);
+        }
+
+        Ok(ConfigBuilder::default()
+            .general(inner.general)
+            .rules(Rules::new(rules).map_err(serde::de::Error::custom)?)
+            .custom(inner.custom)
+            .build())
     }
 }
 
@@ -71,10 +104,150 @@ impl<'a> ConfigLoader<'a> {
         let mut buffer = String::new();
         reader.read_to_string(&mut buffer)?;
 
-        let rule_seed = RulesSeed::new(self.available_rules);
         let toml = toml::Deserializer::parse(&buffer)?;
 
-        let config = ConfigSeed::new(&rule_seed).deserialize(toml)?;
+        let config = ConfigSeed::new(self.available_rules).deserialize(toml)?;
+        Ok(config)
+    }
+
+    /// Load config from merged [layers](layers)
+    pub fn load_table(&self, table: Table) -> Result<Config, Error> {
+        let config =
+            ConfigSeed::new(self.available_rules).deserialize(toml::Value::Table(table))?;
+
         Ok(config)
     }
+
+    /// Load config of vault from `path` with all [layers](layers):
+    /// user-global config, `extends` and configs with the same name in subfolders
+    #[instrument(skip(self), err)]
+    pub fn load_vault(self, path: &Path) -> Result<Config, Error> {
+        debug!("Loading config of vault");
+
+        let mut table = Table::new();
+        let global = layers::global_config_path();
+
+        if global.is_file() {
+            layers::merge(&mut table, layers::read(&global)?);
+        }
+
+        layers::merge(&mut table, layers::read(path)?);
+
+        let root = path.parent().unwrap_or_else(|| Path::new("."));
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        let mut tables: HashMap<PathBuf, Table> = HashMap::new();
+        let mut overrides = Vec::new();
+
+        for dir in layers::nested(root, &name) {
+            let relative = dir.strip_prefix(root).unwrap_or(&dir).to_path_buf();
+
+            // Parent folders are before subfolders, so the nearest parent is already merged
+            let mut nested = relative
+                .ancestors()
+                .skip(1)
+                .find_map(|parent| tables.get(parent))
+                .unwrap_or(&table)
+                .clone();
+
+            layers::merge(&mut nested, layers::read(&dir.join(name.as_ref()))?);
+
+            overrides.push((relative.clone(), self.load_table(nested.clone())?));
+            tables.insert(relative, nested);
+        }
+
+        let config = self.load_table(table)?;
+
+        Ok(ConfigBuilder::default()
+            .general(config.general)
+            .rules(config.rules)
+            .custom(config.custom)
+            .overrides(overrides)
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfigSaver;
+    use obsidian_tidy_core::rule::Severity;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn load_vault() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".obsidian-tidy.toml");
+
+        fs::create_dir_all(dir.path().join("daily/sub")).unwrap();
+        fs::write(
+            &path,
+            "extends = \"standard\"\n[general]\nignore-tags = [\"draft\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("daily/.obsidian-tidy.toml"),
+            "[rules.content.empty-content]\nenable = false\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("daily/sub/.obsidian-tidy.toml"),
+            "[general]\nignore-tags = [\"wip\"]\n",
+        )
+        .unwrap();
+
+        let config = ConfigLoader::default().load_vault(&path).unwrap();
+        let enabled =
+            |path: &str| config.for_note(Path::new(path)).rules()["empty-content"].is_enabled();
+
+        assert!(enabled("note.md"));
+        assert!(!enabled("daily/note.md"));
+        assert!(!enabled("daily/sub/note.md"));
+
+        let sub = config.for_note(Path::new("daily/sub/note.md"));
+        assert_eq!(sub.general().ignore_tags, ["wip"]);
+        assert_eq!(config.general().ignore_tags, ["draft"]);
+        assert_eq!(
+            config
+                .overrides()
+                .iter()
+                .map(|(dir, _)| dir.clone())
+                .collect::<Vec<_>>(),
+            [PathBuf::from("daily"), PathBuf::from("daily/sub")]
+        );
+    }
+
+    #[test]
+    fn custom_rules() {
+        let source = "[rules.content.empty-content]\nenable = true\n\n\
+                      [custom.no-todo]\npattern = \"TODO:\"\nmessage = \"Resolve TODO\"\n\
+                      severity = \"error\"\n";
+
+        let config = ConfigLoader::default()
+            .load(&mut source.as_bytes())
+            .unwrap();
+
+        assert_eq!(config.rules().len(), 2);
+        assert_eq!(config.rules()["no-todo"].severity(), Severity::Error);
+        assert!(config.custom().contains_key("no-todo"));
+
+        let mut saved = Vec::new();
+        ConfigSaver::new(&config).save(&mut saved).unwrap();
+        let saved = String::from_utf8(saved).unwrap();
+
+        assert!(saved.contains("[custom.no-todo]"));
+        assert!(!saved.contains("[rules.other.no-todo]"));
+    }
+
+    #[test]
+    fn custom_rule_with_name_of_rule() {
+        let source = "[custom.empty-content]\npattern = \"a\"\nmessage = \"b\"\n";
+
+        let Err(error) = ConfigLoader::default().load(&mut source.as_bytes()) else {
+            unreachable!("name of custom rule must be unique");
+        };
+
+        assert!(error.to_string().contains("empty-content"));
+    }
 }
@@ -0,0 +1,225 @@
+//! Migration from settings of other linters
+
+use super::{Config, Error, General, builder::ConfigBuilder, template::Template};
+use obsidian_tidy_core::rule::{Options, Rules, SharedErrorRule};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use tracing::{debug, instrument};
+
+/// Settings of obsidian-linter plugin relative to vault
+pub const JS_LINTER_SETTINGS: &str = ".obsidian/plugins/obsidian-linter/data.json";
+
+/// Settings of obsidian-linter which only change behavior of editor
+const EDITOR_SETTINGS: [&str; 7] = [
+    "lintOnSave",
+    "lintOnFileChange",
+    "displayLintOnFileChangeNotice",
+    "displayChanged",
+    "recordLintOnSaveLogs",
+    "logLevel",
+    "settingsConvertedToConfigKeyValues",
+];
+
+/// Rule of obsidian-linter with equivalent rule
+#[derive(Debug, Clone, Copy)]
+struct Mapping {
+    js_rule: &'static str,
+    rule: &'static str,
+
+    /// Options of obsidian-linter with equivalent options
+    options: &'static [(&'static str, &'static str)],
+}
+
+/// Rules of obsidian-linter which have equivalents in [`ALL_RULES`](obsidian_tidy_rules::ALL_RULES)
+const MAPPINGS: &[Mapping] = &[
+    Mapping {
+        js_rule: "yaml-key-sort",
+        rule: "key-order",
+        options: &[],
+    },
+    Mapping {
+        js_rule: "remove-yaml-keys",
+        rule: "forbidden-keys",
+        options: &[],
+    },
+    Mapping {
+        js_rule: "insert-yaml-attributes",
+        rule: "required-keys",
+        options: &[],
+    },
+    Mapping {
+        js_rule: "format-yaml-array",
+        rule: "list-format",
+        options: &[],
+    },
+    Mapping {
+        js_rule: "format-tags-in-yaml",
+        rule: "list-format",
+        options: &[],
+    },
+];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsLinterSettings {
+    rule_configs: BTreeMap<String, Map<String, Value>>,
+    folders_to_ignore: Vec<String>,
+
+    #[serde(flatten)]
+    other: BTreeMap<String, Value>,
+}
+
+/// Config from settings of other linter
+#[derive(Debug)]
+pub struct Migration {
+    pub config: Config,
+
+    /// Settings which have no equivalent
+    pub unmapped: Vec<String>,
+}
+
+/// Options of rule from options of obsidian-linter
+fn options(
+    mapping: &Mapping,
+    js_options: &Map<String, Value>,
+    unmapped: &mut Vec<String>,
+) -> Options {
+    let mut options = Options::new();
+
+    for (js_option, value) in js_options {
+        if js_option == "enabled" {
+            continue;
+        }
+
+        let option = mapping
+            .options
+            .iter()
+            .find(|(js, _)| *js == js_option.as_str())
+            .map(|(_, option)| *option);
+
+        match option.map(|option| (option, toml::Value::try_from(value))) {
+            Some((option, Ok(value))) => {
+                options.insert(option.to_string(), value);
+            }
+            _ => unmapped.push(format!(
+                "Option `{js_option}` of rule `{}` has no equivalent",
+                mapping.js_rule
+            )),
+        }
+    }
+
+    options
+}
+
+/// Migrate `data.json` of obsidian-linter plugin.
+/// Rules without equivalent in obsidian-linter are disabled
+#[instrument(skip(json), err)]
+pub fn from_js_linter(json: &str) -> Result<Migration, Error> {
+    debug!("Migrate from obsidian-linter");
+
+    let settings: JsLinterSettings = serde_json::from_str(json)?;
+    let mut rules: Rules<SharedErrorRule> = Template::Empty.into();
+    let mut unmapped = Vec::new();
+
+    for (js_rule, js_options) in &settings.rule_configs {
+        let enabled = js_options
+            .get("enabled")
+            .and_then(Value::as_bool)
+            .unwrap_or_default();
+
+        let Some(mapping) = MAPPINGS
+            .iter()
+            .find(|mapping| mapping.js_rule == js_rule.as_str())
+        else {
+            // Disabled rules lose nothing
+            if enabled {
+                unmapped.push(format!("Rule `{js_rule}` has no equivalent"));
+            }
+
+            continue;
+        };
+
+        let Some(rule) = rules.get_mut_by_name(mapping.rule) else {
+            continue;
+        };
+
+        let options = options(mapping, js_options, &mut unmapped);
+
+        match rule.clone().configure(options) {
+            Ok(configured) => *rule = configured,
+            Err(error) => unmapped.push(format!("Options of rule `{js_rule}`: {error}")),
+        }
+
+        if enabled {
+            rule.enable();
+        }
+    }
+
+    let exclude = settings
+        .folders_to_ignore
+        .iter()
+        .map(|folder| folder.trim_matches('/'))
+        .filter(|folder| !folder.is_empty())
+        .map(|folder| format!("{folder}/**"))
+        .collect();
+
+    unmapped.extend(
+        settings
+            .other
+            .keys()
+            .filter(|setting| !EDITOR_SETTINGS.contains(&setting.as_str()))
+            .map(|setting| format!("Setting `{setting}` has no equivalent")),
+    );
+
+    let config = ConfigBuilder::default()
+        .general(General {
+            exclude,
+            ..General::default()
+        })
+        .rules(rules)
+        .build();
+
+    Ok(Migration { config, unmapped })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn js_linter() {
+        let json = r#"{
+            "ruleConfigs": {
+                "yaml-title": { "enabled": true, "title-key": "title" },
+                "yaml-key-sort": { "enabled": true, "yaml-key-priority-sort-order": "title" },
+                "capitalize-headings": { "enabled": false }
+            },
+            "lintOnSave": true,
+            "foldersToIgnore": ["templates", "archive/"],
+            "filesToIgnore": [{ "match": "^daily", "flags": "", "label": "" }]
+        }"#;
+
+        let migration = from_js_linter(json).unwrap();
+
+        assert_eq!(
+            migration.config.general().exclude,
+            ["templates/**", "archive/**"]
+        );
+        assert!(migration.config.rules()["empty-content"].is_disabled());
+        assert!(migration.config.rules()["key-order"].is_enabled());
+        assert_eq!(
+            migration.unmapped,
+            [
+                "Option `yaml-key-priority-sort-order` of rule `yaml-key-sort` has no equivalent",
+                "Rule `yaml-title` has no equivalent",
+                "Setting `filesToIgnore` has no equivalent",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(matches!(from_js_linter("{"), Err(Error::Json(_))));
+    }
+}
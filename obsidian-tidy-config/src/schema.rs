@@ -0,0 +1,263 @@
+//! JSON Schema of config for editors
+
+use super::{custom::CUSTOM, layers::EXTENDS};
+use obsidian_tidy_core::rule::{Category, Rule, Severity, SharedErrorRule};
+use serde_json::{Map, Value, json};
+use toml::Value as TomlValue;
+
+/// Schema of array of strings
+fn strings(description: &str) -> Value {
+    json!({
+        "description": description,
+        "type": "array",
+        "items": { "type": "string" },
+    })
+}
+
+/// Schema of object which has only `properties`
+fn object(properties: Map<String, Value>) -> Value {
+    let mut schema = Map::new();
+    schema.insert("type".to_string(), "object".into());
+    schema.insert("properties".to_string(), Value::Object(properties));
+    schema.insert("additionalProperties".to_string(), false.into());
+
+    Value::Object(schema)
+}
+
+/// JSON type of option by its default value
+const fn json_type(value: &TomlValue) -> &'static str {
+    match value {
+        TomlValue::String(_) | TomlValue::Datetime(_) => "string",
+        TomlValue::Integer(_) => "integer",
+        TomlValue::Float(_) => "number",
+        TomlValue::Boolean(_) => "boolean",
+        TomlValue::Array(_) => "array",
+        TomlValue::Table(_) => "object",
+    }
+}
+
+fn rule(rule: &SharedErrorRule) -> Value {
+    let severities: Vec<_> = Severity::ALL.map(Severity::as_str).into();
+
+    let mut properties = Map::new();
+    properties.insert(
+        "enable".to_string(),
+        json!({ "description": "Check notes by this rule", "type": "boolean" }),
+    );
+    properties.insert(
+        "severity".to_string(),
+        json!({
+            "description": "Severity of violations",
+            "enum": severities,
+            "default": rule.default_severity().as_str(),
+        }),
+    );
+    properties.insert(
+        "include".to_string(),
+        strings("Globs of notes which are checked. Empty means all notes"),
+    );
+    properties.insert(
+        "exclude".to_string(),
+        strings("Globs of notes which are skipped"),
+    );
+    properties.insert(
+        "ignore-tags".to_string(),
+        strings("Tags of notes which are skipped"),
+    );
+
+    let docs = rule.docs();
+
+    for (name, default) in rule.default_options() {
+        let mut option = json!({
+            "type": json_type(&default),
+            "default": default,
+        });
+
+        if let Some(description) = docs.options().get(&name) {
+            option["description"] = description.as_str().into();
+        }
+
+        properties.insert(name, option);
+    }
+
+    let mut schema = object(properties);
+    schema["description"] = rule.description().into();
+
+    schema
+}
+
+/// Schema of custom rule
+fn custom_rule() -> Value {
+    let severities: Vec<_> = Severity::ALL.map(Severity::as_str).into();
+    let categories: Vec<_> = Category::ALL.iter().map(Category::as_str).collect();
+
+    let mut properties = Map::new();
+    properties.insert(
+        "pattern".to_string(),
+        json!({ "description": "Regex of forbidden text", "type": "string" }),
+    );
+    properties.insert(
+        "message".to_string(),
+        json!({ "description": "Message of violation", "type": "string" }),
+    );
+    properties.insert(
+        "replacement".to_string(),
+        json!({
+            "description": "Replacement of match for fix, `$1` and `$name` are expanded to groups",
+            "type": "string",
+        }),
+    );
+    properties.insert(
+        "description".to_string(),
+        json!({ "description": "Description of rule", "type": "string" }),
+    );
+    properties.insert(
+        "category".to_string(),
+        json!({ "enum": categories, "default": Category::Other.as_str() }),
+    );
+    properties.insert(
+        "enable".to_string(),
+        json!({ "description": "Check notes by this rule", "type": "boolean", "default": true }),
+    );
+    properties.insert(
+        "severity".to_string(),
+        json!({
+            "description": "Severity of violations",
+            "enum": severities,
+            "default": Severity::default().as_str(),
+        }),
+    );
+    properties.insert(
+        "include".to_string(),
+        strings("Globs of notes which are checked. Empty means all notes"),
+    );
+    properties.insert(
+        "exclude".to_string(),
+        strings("Globs of notes which are skipped"),
+    );
+    properties.insert(
+        "ignore-tags".to_string(),
+        strings("Tags of notes which are skipped"),
+    );
+
+    let mut schema = object(properties);
+    schema["required"] = json!(["pattern", "message"]);
+
+    schema
+}
+
+/// JSON Schema of config with `available_rules`
+#[must_use]
+pub fn schema(available_rules: &[SharedErrorRule]) -> Value {
+    let mut categories = Map::new();
+
+    for category in Category::ALL {
+        let rules = available_rules
+            .iter()
+            .filter(|rule| rule.category() == category)
+            .map(|available| (available.name().to_string(), rule(available)))
+            .collect();
+
+        categories.insert(category.as_str().to_string(), object(rules));
+    }
+
+    let mut general = Map::new();
+    general.insert(
+        "exclude".to_string(),
+        strings("Globs of notes which are never linted. Relative to vault"),
+    );
+    general.insert(
+        "respect-gitignore".to_string(),
+        json!({
+            "description": "Skip notes ignored by `.gitignore`",
+            "type": "boolean",
+            "default": true,
+        }),
+    );
+    general.insert(
+        "ignore-tags".to_string(),
+        strings("Tags of notes which are never linted, like `#draft`"),
+    );
+
+    let mut properties = Map::new();
+    properties.insert(
+        EXTENDS.to_string(),
+        json!({
+            "description": "Extended configs: paths to `.toml` files or presets `all`, `standard`, `empty`",
+            "anyOf": [
+                { "type": "string" },
+                { "type": "array", "items": { "type": "string" } },
+            ],
+        }),
+    );
+    properties.insert("general".to_string(), object(general));
+    properties.insert("rules".to_string(), object(categories));
+    properties.insert(
+        CUSTOM.to_string(),
+        json!({
+            "description": "Regex rules by names",
+            "type": "object",
+            "additionalProperties": custom_rule(),
+        }),
+    );
+
+    let mut schema = object(properties);
+    schema["$schema"] = "http://json-schema.org/draft-07/schema#".into();
+    schema["title"] = "obsidian-tidy config".into();
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConfigSaver, builder::ConfigBuilder, template::Template};
+    use obsidian_tidy_rules::ALL_RULES;
+
+    /// Every key of `value` is described by `schema`
+    fn assert_covers(schema: &Value, value: &Value, path: &str) {
+        let Value::Object(object) = value else {
+            return;
+        };
+
+        for (key, value) in object {
+            let Some(property) = schema["properties"].get(key) else {
+                unreachable!("`{path}.{key}` isn't in schema");
+            };
+
+            assert_covers(property, value, &format!("{path}.{key}"));
+        }
+    }
+
+    #[test]
+    fn covers_template_all() {
+        let config = ConfigBuilder::default().rules(Template::All.into()).build();
+
+        let mut toml = Vec::new();
+        ConfigSaver::new(&config).save(&mut toml).unwrap();
+
+        let table: toml::Table = toml::from_str(&String::from_utf8(toml).unwrap()).unwrap();
+        let value = serde_json::to_value(table).unwrap();
+
+        assert_covers(&schema(&ALL_RULES), &value, "");
+    }
+
+    #[test]
+    fn options_with_defaults() {
+        let schema = schema(&ALL_RULES);
+        let option = &schema["properties"]["rules"]["properties"]["content"]["properties"]["empty-content"]
+            ["properties"]["min-words"];
+
+        assert_eq!(option["type"], "integer");
+        assert_eq!(option["default"], 1);
+    }
+
+    #[test]
+    fn custom_rules() {
+        let schema = schema(&ALL_RULES);
+        let rule = &schema["properties"]["custom"]["additionalProperties"];
+
+        assert_eq!(rule["required"], json!(["pattern", "message"]));
+        assert_eq!(rule["properties"]["category"]["default"], "other");
+    }
+}
@@ -30,6 +30,9 @@ static STANDARD: LazyLock<Rules<SharedErrorRule>> = LazyLock::new(|| {
     let mut rules = EMPTY.clone();
 
     rules["empty-content"].enable();
+    rules["invalid-frontmatter"].enable();
+    rules["duplicate-keys"].enable();
+    rules["list-format"].enable();
 
     rules
 });
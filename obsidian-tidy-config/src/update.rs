@@ -0,0 +1,237 @@
+//! Update config file for the current set of rules
+//!
+//! Rules which are missing in config are added with state from [`Template`],
+//! rules which don't exist anymore are removed.
+//! Comments and formatting of config are preserved
+
+use super::{Error, layers, loader::ConfigLoader, template::Template};
+use obsidian_tidy_core::rule::{Rule, SharedErrorRule, ToggleableRule, rules::serde::RuleConfig};
+use obsidian_tidy_rules::ALL_RULES;
+use std::{collections::HashSet, path::Path};
+use toml_edit::{DocumentMut, Item, Table};
+use tracing::{debug, instrument};
+
+/// Result of update
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Update {
+    /// Config after update
+    pub source: String,
+
+    /// Rules which are added from template
+    pub added: Vec<String>,
+
+    /// Rules which don't exist anymore and are removed
+    pub removed: Vec<String>,
+}
+
+impl Update {
+    /// Config isn't changed
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct Updater<'a> {
+    available_rules: &'a Vec<SharedErrorRule>,
+    template: Template,
+}
+
+impl Updater<'_> {
+    #[must_use]
+    pub fn new(template: Template) -> Self {
+        Self {
+            available_rules: &ALL_RULES,
+            template,
+        }
+    }
+}
+
+impl<'a> Updater<'a> {
+    #[must_use]
+    pub const fn available_rules(mut self, available_rules: &'a Vec<SharedErrorRule>) -> Self {
+        self.available_rules = available_rules;
+        self
+    }
+
+    /// Rule from template. Rules which aren't in template are disabled
+    fn template_rule(&self, rule: &SharedErrorRule) -> ToggleableRule<SharedErrorRule> {
+        self.template
+            .iter()
+            .find(|template| template.name() == rule.name())
+            .cloned()
+            .unwrap_or_else(|| ToggleableRule::new(rule.clone(), false))
+    }
+
+    /// Table of rule like in config written by [`ConfigSaver`](super::ConfigSaver)
+    fn rule_table(rule: &ToggleableRule<SharedErrorRule>) -> Result<Table, Error> {
+        let config = RuleConfig {
+            enable: rule.is_enabled(),
+            severity: rule.severity_override(),
+            include: rule.paths().include().to_vec(),
+            exclude: rule.paths().exclude().to_vec(),
+            ignore_tags: rule.ignore_tags().to_vec(),
+            options: rule.merged_options(),
+        };
+
+        let document: DocumentMut = toml::to_string(&config)?.parse()?;
+
+        // New table without position is placed at the end of config
+        let mut table = Table::new();
+        for (key, item) in document.iter() {
+            table.insert(key, item.clone());
+        }
+
+        Ok(table)
+    }
+
+    /// Std table in `item`. Inline table is converted to std table
+    fn std_table(item: &mut Item) -> Option<&mut Table> {
+        if let Some(inline) = item.as_inline_table() {
+            *item = Item::Table(inline.clone().into_table());
+        }
+
+        item.as_table_mut()
+    }
+
+    /// Update config file with `source` in `path`.
+    /// Rules are added only if config doesn't `extends` other configs:
+    /// otherwise they come from extended configs
+    #[instrument(skip(self, source), err)]
+    pub fn update(&self, source: &str, path: &Path) -> Result<Update, Error> {
+        debug!("Updating config");
+
+        let mut document: DocumentMut = source.parse()?;
+        let mut present = HashSet::new();
+        let mut removed = Vec::new();
+
+        if let Some(categories) = document.get_mut("rules").and_then(Item::as_table_like_mut) {
+            for (_, item) in categories.iter_mut() {
+                let Some(rules) = item.as_table_like_mut() else {
+                    continue;
+                };
+
+                let unknown: Vec<String> = rules
+                    .iter()
+                    .map(|(name, _)| name.to_string())
+                    .filter(|name| !self.available_rules.iter().any(|rule| rule.name() == name))
+                    .collect();
+
+                for name in unknown {
+                    rules.remove(&name);
+                    removed.push(name);
+                }
+
+                present.extend(rules.iter().map(|(name, _)| name.to_string()));
+            }
+        }
+
+        let mut added = Vec::new();
+
+        if !document.contains_key(layers::EXTENDS) {
+            for rule in self
+                .available_rules
+                .iter()
+                .filter(|rule| !present.contains(rule.name()))
+            {
+                let mut implicit = Table::new();
+                implicit.set_implicit(true);
+
+                let Some(categories) = Self::std_table(
+                    document
+                        .entry("rules")
+                        .or_insert_with(|| Item::Table(implicit.clone())),
+                ) else {
+                    return Err(Error::InvalidRules);
+                };
+
+                let Some(rules) = Self::std_table(
+                    categories
+                        .entry(rule.category().as_str())
+                        .or_insert_with(|| Item::Table(implicit.clone())),
+                ) else {
+                    return Err(Error::InvalidRules);
+                };
+
+                rules.insert(
+                    rule.name(),
+                    Item::Table(Self::rule_table(&self.template_rule(rule))?),
+                );
+                added.push(rule.name().to_string());
+            }
+        }
+
+        let source = document.to_string();
+
+        // Updated config must be loaded
+        let table = layers::resolve(path, toml::from_str(&source)?)?;
+        ConfigLoader::new(self.available_rules).load_table(table)?;
+
+        Ok(Update {
+            source,
+            added,
+            removed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::ConfigBuilder;
+
+    fn update(source: &str, template: Template) -> Update {
+        Updater::new(template)
+            .update(source, Path::new("config.toml"))
+            .unwrap()
+    }
+
+    #[test]
+    fn add_rules() {
+        let source = "# My vault\n[general]\nignore-tags = [\"draft\"] # drafts\n";
+        let update = update(source, Template::All);
+
+        assert_eq!(
+            update.added,
+            ALL_RULES.iter().map(Rule::name).collect::<Vec<_>>()
+        );
+        assert!(update.removed.is_empty());
+        assert!(update.source.starts_with(source));
+        assert!(
+            update
+                .source
+                .contains("[rules.content.empty-content]\nenable = true\n")
+        );
+    }
+
+    #[test]
+    fn remove_rules() {
+        let source = "[rules.content.empty-content]\nenable = false # off\n\n\
+                      [rules.content.old-rule]\nenable = true\n";
+        let update = update(source, Template::All);
+
+        assert!(!update.added.contains(&"empty-content".to_string()));
+        assert_eq!(update.removed, ["old-rule"]);
+        assert!(update.source.contains("enable = false # off"));
+        assert!(!update.source.contains("old-rule"));
+    }
+
+    #[test]
+    fn up_to_date() {
+        let config = ConfigBuilder::default()
+            .rules(Template::Empty.into())
+            .build();
+        let source = toml::to_string(&config).unwrap();
+        let update = update(&source, Template::All);
+
+        assert!(update.is_empty());
+        assert_eq!(update.source, source);
+    }
+
+    #[test]
+    fn extends() {
+        let update = update("extends = \"standard\"\n", Template::All);
+        assert!(update.is_empty());
+    }
+}
@@ -0,0 +1,790 @@
+//! Validation of config files
+//!
+//! Unlike [`ConfigLoader`], validation doesn't stop at the first problem
+//! and reports position of every problem in config file
+
+use super::{Error, custom::CUSTOM, layers, loader::ConfigLoader, template::Template};
+use clap::ValueEnum;
+use obsidian_tidy_core::rule::{
+    Category, Options, PathFilter, Rule, Severity, SharedErrorRule, options::Error as OptionsError,
+};
+use obsidian_tidy_rules::ALL_RULES;
+use std::{
+    collections::HashSet,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use toml_edit::{ImDocument, Item, Key, TableLike, Value};
+use tracing::{debug, instrument};
+
+/// Top-level keys of config
+const KEYS: [&str; 4] = ["general", "rules", CUSTOM, layers::EXTENDS];
+
+/// Keys of `[general]`
+const GENERAL_KEYS: [&str; 3] = ["exclude", "respect-gitignore", "ignore-tags"];
+
+/// Keys of every rule. Other keys are options of rule
+const RULE_KEYS: [&str; 5] = ["enable", "severity", "include", "exclude", "ignore-tags"];
+
+/// Keys of every custom rule
+const CUSTOM_KEYS: [&str; 10] = [
+    "pattern",
+    "message",
+    "replacement",
+    "description",
+    "category",
+    "enable",
+    "severity",
+    "include",
+    "exclude",
+    "ignore-tags",
+];
+
+/// Minimal similarity of names for "did you mean" suggestion
+const MIN_SIMILARITY: f64 = 0.8;
+
+/// Problem of config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+
+    /// Byte range in config file. Empty if problem has no position
+    pub location: Range<usize>,
+}
+
+/// Config file with its problems
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProblems {
+    pub path: PathBuf,
+    pub source: String,
+    pub problems: Vec<Problem>,
+}
+
+/// The most similar to `name` of `candidates`
+#[must_use]
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|(similarity, _)| *similarity >= MIN_SIMILARITY)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate)
+}
+
+fn did_you_mean<'a>(
+    message: String,
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> String {
+    match suggest(name, candidates) {
+        Some(suggestion) => format!("{message}, did you mean `{suggestion}`?"),
+        None => message,
+    }
+}
+
+/// Entries of table with keys, because only keys know their positions
+fn entries(table: &dyn TableLike) -> Vec<(&Key, &Item)> {
+    table
+        .iter()
+        .filter_map(|(name, _)| table.get_key_value(name))
+        .collect()
+}
+
+/// State of validation of one config file
+#[derive(Debug)]
+struct Check<'a> {
+    available_rules: &'a [SharedErrorRule],
+
+    /// Folder of config file for relative paths in `extends`
+    dir: &'a Path,
+
+    /// The same config file for values of options
+    table: toml::Table,
+
+    /// Names of rules which are already validated
+    seen: HashSet<String>,
+
+    problems: Vec<Problem>,
+
+    /// Extended config files
+    extends: Vec<PathBuf>,
+}
+
+impl<'a> Check<'a> {
+    fn new(available_rules: &'a [SharedErrorRule], dir: &'a Path) -> Self {
+        Self {
+            available_rules,
+            dir,
+            table: toml::Table::new(),
+            seen: HashSet::new(),
+            problems: Vec::new(),
+            extends: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, severity: Severity, message: String, location: Option<Range<usize>>) {
+        self.problems.push(Problem {
+            severity,
+            message,
+            location: location.unwrap_or_default(),
+        });
+    }
+
+    fn error(&mut self, message: String, location: Option<Range<usize>>) {
+        self.push(Severity::Error, message, location);
+    }
+
+    fn expected(&mut self, item: &Item, what: &str) {
+        self.error(
+            format!("Expected {what}, found {}", item.type_name()),
+            item.span(),
+        );
+    }
+
+    fn config(&mut self, source: &str) {
+        let document = match ImDocument::parse(source) {
+            Ok(document) => document,
+            Err(error) => {
+                self.error(error.message().trim().to_string(), error.span());
+                return;
+            }
+        };
+
+        self.table = toml::from_str(source).unwrap_or_default();
+
+        for (key, item) in entries(document.as_table()) {
+            match key.get() {
+                "general" => self.general(item),
+                "rules" => self.rules(item),
+                CUSTOM => self.custom(item),
+                layers::EXTENDS => self.extends(item),
+                name => self.error(
+                    did_you_mean(format!("Unknown key `{name}`"), name, KEYS),
+                    key.span(),
+                ),
+            }
+        }
+    }
+
+    fn general(&mut self, item: &Item) {
+        let Some(table) = item.as_table_like() else {
+            self.expected(item, "a table");
+            return;
+        };
+
+        for (key, item) in entries(table) {
+            match key.get() {
+                "exclude" => self.globs(item),
+                "ignore-tags" => {
+                    self.strings(item);
+                }
+                "respect-gitignore" => {
+                    if !item.is_bool() {
+                        self.expected(item, "a boolean");
+                    }
+                }
+                name => self.error(
+                    did_you_mean(
+                        format!("Unknown key `{name}` in `[general]`"),
+                        name,
+                        GENERAL_KEYS,
+                    ),
+                    key.span(),
+                ),
+            }
+        }
+    }
+
+    fn extends(&mut self, item: &Item) {
+        let values: Vec<&Value> = match item.as_value() {
+            Some(Value::Array(array)) => array.iter().collect(),
+            Some(value) => vec![value],
+            None => {
+                self.expected(item, "a string or an array of strings");
+                return;
+            }
+        };
+
+        let presets: Vec<String> = Template::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .map(|value| value.get_name().to_string())
+            .collect();
+
+        for value in values {
+            let Some(extends) = value.as_str() else {
+                self.error(
+                    format!("Expected a string, found {}", value.type_name()),
+                    value.span(),
+                );
+                continue;
+            };
+
+            if layers::is_path(extends) {
+                let path = self.dir.join(extends);
+
+                if path.is_file() {
+                    self.extends.push(path);
+                } else {
+                    self.error(
+                        format!("Extended config `{extends}` not found"),
+                        value.span(),
+                    );
+                }
+            } else if layers::preset(extends).is_err() {
+                self.error(
+                    did_you_mean(
+                        format!("Unknown preset `{extends}`"),
+                        extends,
+                        presets.iter().map(String::as_str),
+                    ),
+                    value.span(),
+                );
+            }
+        }
+    }
+
+    fn rules(&mut self, item: &Item) {
+        let Some(categories) = item.as_table_like() else {
+            self.expected(item, "a table");
+            return;
+        };
+
+        for (key, item) in entries(categories) {
+            let name = key.get();
+
+            let Some(category) = Category::ALL
+                .into_iter()
+                .find(|category| category.as_str() == name)
+            else {
+                let message = match self.available_rules.iter().find(|rule| rule.name() == name) {
+                    Some(rule) => format!(
+                        "Rule `{name}` must be in table `[rules.{}.{name}]`",
+                        rule.category().as_str()
+                    ),
+                    None => did_you_mean(
+                        format!("Unknown category `{name}`"),
+                        name,
+                        Category::ALL.iter().map(Category::as_str),
+                    ),
+                };
+
+                self.error(message, key.span());
+                continue;
+            };
+
+            let Some(rules) = item.as_table_like() else {
+                self.expected(item, "a table");
+                continue;
+            };
+
+            for (key, item) in entries(rules) {
+                self.rule(&category, key, item);
+            }
+        }
+    }
+
+    fn rule(&mut self, category: &Category, key: &Key, item: &Item) {
+        let name = key.get();
+
+        let Some(rule) = self
+            .available_rules
+            .iter()
+            .find(|rule| rule.name() == name)
+            .cloned()
+        else {
+            let message = did_you_mean(
+                format!("Unknown rule `{name}`"),
+                name,
+                self.available_rules.iter().map(Rule::name),
+            );
+
+            self.error(message, key.span());
+            return;
+        };
+
+        if !self.seen.insert(name.to_string()) {
+            self.error(
+                format!("Rule `{name}` is configured more than once"),
+                key.span(),
+            );
+        }
+
+        if rule.category() != *category {
+            self.push(
+                Severity::Warning,
+                format!(
+                    "Rule `{name}` belongs to category `{}`, move it to `[rules.{}.{name}]`",
+                    rule.category().as_str(),
+                    rule.category().as_str()
+                ),
+                key.span(),
+            );
+        }
+
+        let Some(table) = item.as_table_like() else {
+            self.expected(item, "a table");
+            return;
+        };
+
+        let default_options = rule.default_options();
+        let mut options = Options::new();
+
+        for (key, item) in entries(table) {
+            match key.get() {
+                "enable" => {
+                    if !item.is_bool() {
+                        self.expected(item, "a boolean");
+                    }
+                }
+                "severity" => self.severity(item),
+                "include" | "exclude" => self.globs(item),
+                "ignore-tags" => {
+                    self.strings(item);
+                }
+                option if default_options.contains_key(option) => {
+                    let value = self
+                        .table
+                        .get("rules")
+                        .and_then(|rules| rules.get(category.as_str()))
+                        .and_then(|rules| rules.get(name))
+                        .and_then(|rule| rule.get(option));
+
+                    if let Some(value) = value {
+                        options.insert(option.to_string(), value.clone());
+                    }
+                }
+                option => {
+                    let candidates = RULE_KEYS
+                        .into_iter()
+                        .chain(default_options.keys().map(String::as_str));
+
+                    self.error(
+                        did_you_mean(
+                            format!("Unknown option `{option}` of rule `{name}`"),
+                            option,
+                            candidates,
+                        ),
+                        key.span(),
+                    );
+                }
+            }
+        }
+
+        if let Err(OptionsError::Invalid(error)) = rule.with_options(&options) {
+            self.error(
+                format!("Invalid options of rule `{name}`: {}", error.message()),
+                item.span().or_else(|| key.span()),
+            );
+        }
+    }
+
+    fn custom(&mut self, item: &Item) {
+        let Some(rules) = item.as_table_like() else {
+            self.expected(item, "a table");
+            return;
+        };
+
+        for (key, item) in entries(rules) {
+            self.custom_rule(key, item);
+        }
+    }
+
+    fn custom_rule(&mut self, key: &Key, item: &Item) {
+        let name = key.get();
+
+        if self.available_rules.iter().any(|rule| rule.name() == name) {
+            self.error(
+                format!("Custom rule `{name}` has the same name as another rule"),
+                key.span(),
+            );
+        }
+
+        let Some(table) = item.as_table_like() else {
+            self.expected(item, "a table");
+            return;
+        };
+
+        for required in ["pattern", "message"] {
+            if !table.contains_key(required) {
+                self.error(
+                    format!("Custom rule `{name}` has no `{required}`"),
+                    key.span(),
+                );
+            }
+        }
+
+        for (key, item) in entries(table) {
+            match key.get() {
+                "pattern" => {
+                    let Some(pattern) = item.as_str() else {
+                        self.expected(item, "a string");
+                        continue;
+                    };
+
+                    if let Err(error) = regex::Regex::new(pattern) {
+                        self.error(
+                            format!("Invalid pattern of custom rule `{name}`: {error}"),
+                            item.span(),
+                        );
+                    }
+                }
+                "message" | "replacement" | "description" => {
+                    if !item.is_str() {
+                        self.expected(item, "a string");
+                    }
+                }
+                "category" => self.category(item),
+                "enable" => {
+                    if !item.is_bool() {
+                        self.expected(item, "a boolean");
+                    }
+                }
+                "severity" => self.severity(item),
+                "include" | "exclude" => self.globs(item),
+                "ignore-tags" => {
+                    self.strings(item);
+                }
+                option => self.error(
+                    did_you_mean(
+                        format!("Unknown key `{option}` of custom rule `{name}`"),
+                        option,
+                        CUSTOM_KEYS,
+                    ),
+                    key.span(),
+                ),
+            }
+        }
+    }
+
+    fn category(&mut self, item: &Item) {
+        let Some(category) = item.as_str() else {
+            self.expected(item, "a string");
+            return;
+        };
+
+        if !Category::ALL.iter().any(|known| known.as_str() == category) {
+            self.error(
+                did_you_mean(
+                    format!("Unknown category `{category}`"),
+                    category,
+                    Category::ALL.iter().map(Category::as_str),
+                ),
+                item.span(),
+            );
+        }
+    }
+
+    fn severity(&mut self, item: &Item) {
+        let Some(severity) = item.as_str() else {
+            self.expected(item, "a string");
+            return;
+        };
+
+        if severity.parse::<Severity>().is_err() {
+            self.error(
+                did_you_mean(
+                    format!("Unknown severity `{severity}`"),
+                    severity,
+                    Severity::ALL.map(Severity::as_str),
+                ),
+                item.span(),
+            );
+        }
+    }
+
+    /// Strings of array with their positions
+    fn strings<'i>(&mut self, item: &'i Item) -> Vec<(&'i str, Option<Range<usize>>)> {
+        let Some(array) = item.as_array() else {
+            self.expected(item, "an array of strings");
+            return Vec::new();
+        };
+
+        let mut strings = Vec::with_capacity(array.len());
+
+        for value in array {
+            match value.as_str() {
+                Some(string) => strings.push((string, value.span())),
+                None => self.error(
+                    format!("Expected a string, found {}", value.type_name()),
+                    value.span(),
+                ),
+            }
+        }
+
+        strings
+    }
+
+    fn globs(&mut self, item: &Item) {
+        for (glob, location) in self.strings(item) {
+            if let Err(error) = PathFilter::new([glob], Vec::<String>::new()) {
+                self.error(error.to_string(), location);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Validator<'a> {
+    available_rules: &'a Vec<SharedErrorRule>,
+}
+
+impl Default for Validator<'_> {
+    fn default() -> Self {
+        Self::new(&ALL_RULES)
+    }
+}
+
+impl<'a> Validator<'a> {
+    #[must_use]
+    pub const fn new(available_rules: &'a Vec<SharedErrorRule>) -> Self {
+        Self { available_rules }
+    }
+
+    #[must_use]
+    pub const fn available_rules(mut self, available_rules: &'a Vec<SharedErrorRule>) -> Self {
+        self.available_rules = available_rules;
+        self
+    }
+
+    fn check<'c>(&'c self, source: &str, path: &'c Path) -> Check<'c> {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut check = Check::new(self.available_rules, dir);
+
+        check.config(source);
+        check
+    }
+
+    /// All problems of config file with `source`.
+    /// `path` of config file is used to find configs from `extends`
+    #[must_use]
+    pub fn validate(&self, source: &str, path: &Path) -> Vec<Problem> {
+        self.check(source, path).problems
+    }
+
+    /// Validate config of vault in `path` with all its [layers](layers).
+    /// If every file is valid, also try to load config
+    #[instrument(skip(self), err)]
+    pub fn validate_vault(&self, path: &Path) -> Result<Vec<FileProblems>, Error> {
+        debug!("Validating config of vault");
+
+        let root = path.parent().unwrap_or_else(|| Path::new("."));
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let global = layers::global_config_path();
+
+        let mut paths: Vec<PathBuf> = global
+            .is_file()
+            .then_some(global)
+            .into_iter()
+            .chain([path.to_path_buf()])
+            .chain(
+                layers::nested(root, &name)
+                    .into_iter()
+                    .map(|dir| dir.join(name.as_ref())),
+            )
+            .collect();
+
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+        let mut index = 0;
+
+        // Extended configs are appended to `paths` while validating
+        while let Some(path) = paths.get(index).cloned() {
+            index += 1;
+
+            if !seen.insert(fs::canonicalize(&path)?) {
+                continue;
+            }
+
+            let source = fs::read_to_string(&path)?;
+            let check = self.check(&source, &path);
+            let problems = check.problems;
+
+            paths.extend(check.extends);
+            files.push(FileProblems {
+                path,
+                source,
+                problems,
+            });
+        }
+
+        let valid = files
+            .iter()
+            .flat_map(|file| &file.problems)
+            .all(|problem| problem.severity != Severity::Error);
+
+        if !valid {
+            return Ok(files);
+        }
+
+        let loaded = ConfigLoader::new(self.available_rules).load_vault(path);
+
+        if let (Err(error), Some(file)) = (loaded, files.iter_mut().find(|file| file.path == path))
+        {
+            file.problems.push(Problem {
+                severity: Severity::Error,
+                message: error.to_string(),
+                location: 0..0,
+            });
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn validate(source: &str) -> Vec<Problem> {
+        Validator::default().validate(source, Path::new("config.toml"))
+    }
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(
+            suggest("empty-contnet", ["other", "empty-content"]),
+            Some("empty-content")
+        );
+        assert_eq!(suggest("abc", ["empty-content"]), None);
+    }
+
+    #[test]
+    fn unknown_rule() {
+        let source = "[rules.content.empty-contnet]\nenable = true\n";
+        let problems = validate(source);
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, Severity::Error);
+        assert_eq!(
+            problems[0].message,
+            "Unknown rule `empty-contnet`, did you mean `empty-content`?"
+        );
+        assert_eq!(&source[problems[0].location.clone()], "empty-contnet");
+    }
+
+    #[test]
+    fn wrong_category() {
+        let problems = validate("[rules.spacing.empty-content]\nenable = true\n");
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, Severity::Warning);
+        assert!(
+            problems[0]
+                .message
+                .contains("[rules.content.empty-content]")
+        );
+    }
+
+    #[test]
+    fn all_problems() {
+        let source = "extend = \"standard\"\n\
+                      [general]\n\
+                      exlude = []\n\
+                      [rules.content.empty-content]\n\
+                      enable = \"yes\"\n\
+                      severity = \"warn\"\n\
+                      min-word = 2\n";
+
+        let messages: Vec<_> = validate(source)
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect();
+
+        assert_eq!(
+            messages,
+            [
+                "Unknown key `extend`, did you mean `extends`?",
+                "Unknown key `exlude` in `[general]`, did you mean `exclude`?",
+                "Expected a boolean, found string",
+                "Unknown severity `warn`, did you mean `warning`?",
+                "Unknown option `min-word` of rule `empty-content`, did you mean `min-words`?",
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_rules() {
+        let source = "[custom.no-todo]\n\
+                      pattern = \"TODO(\"\n\
+                      category = \"contnet\"\n\
+                      replacment = \"\"\n\
+                      [custom.empty-content]\n\
+                      pattern = \"a\"\n\
+                      message = \"b\"\n";
+
+        let messages: Vec<_> = validate(source)
+            .into_iter()
+            .map(|problem| problem.message)
+            .collect();
+
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[0], "Custom rule `no-todo` has no `message`");
+        assert!(messages[1].starts_with("Invalid pattern of custom rule `no-todo`"));
+        assert_eq!(
+            messages[2],
+            "Unknown category `contnet`, did you mean `content`?"
+        );
+        assert_eq!(
+            messages[3],
+            "Unknown key `replacment` of custom rule `no-todo`, did you mean `replacement`?"
+        );
+        assert_eq!(
+            messages[4],
+            "Custom rule `empty-content` has the same name as another rule"
+        );
+    }
+
+    #[test]
+    fn invalid_toml() {
+        let problems = validate("[rules\n");
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn validate_vault() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+
+        fs::create_dir(dir.path().join("daily")).unwrap();
+        fs::write(&path, "extends = \"base.toml\"\n").unwrap();
+        fs::write(
+            dir.path().join("base.toml"),
+            "[rules.content.empty-content]\nseverity = \"error\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("daily/config.toml"),
+            "[rules.content.empty-contnet]\nenable = true\n",
+        )
+        .unwrap();
+
+        let files = Validator::default().validate_vault(&path).unwrap();
+        let paths: Vec<_> = files.iter().map(|file| file.path.clone()).collect();
+
+        assert!(paths.contains(&dir.path().join("base.toml")));
+        assert!(paths.contains(&dir.path().join("daily/config.toml")));
+        assert_eq!(
+            files.iter().map(|file| file.problems.len()).sum::<usize>(),
+            1
+        );
+
+        // Every file is valid, but `enable` is missed after merge
+        fs::remove_dir_all(dir.path().join("daily")).unwrap();
+
+        let files = Validator::default().validate_vault(&path).unwrap();
+        let problems = &files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap()
+            .problems;
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("enable"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Fenced code blocks of note
+//!
+//! Block starts with at least three backticks or tildes indented by at most three spaces
+//! and ends with fence of the same character which isn't shorter.
+//! Unclosed block lasts until the end of note
+
+use std::ops::Range;
+
+/// Character and length of fence which starts `line`
+fn fence(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start_matches(' ');
+
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+
+    let character = trimmed
+        .chars()
+        .next()
+        .filter(|character| matches!(character, '`' | '~'))?;
+    let length = trimmed
+        .chars()
+        .take_while(|other| *other == character)
+        .count();
+
+    (length >= 3).then_some((character, length))
+}
+
+/// Byte ranges of fenced code blocks with their fences
+#[must_use]
+pub fn find(source: &str) -> Vec<Range<usize>> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(char, usize, usize)> = None;
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let Some((character, length)) = fence(line) else {
+            continue;
+        };
+
+        let rest = &line.trim_start_matches(' ')[length..];
+
+        match open {
+            // Info string of backtick fence can't contain backticks, like in "```code```"
+            None if character == '~' || !rest.contains('`') => {
+                open = Some((character, length, start));
+            }
+            Some((open_character, open_length, block_start))
+                if character == open_character
+                    && length >= open_length
+                    && rest.trim().is_empty() =>
+            {
+                blocks.push(block_start..offset);
+                open = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((_, _, block_start)) = open {
+        blocks.push(block_start..source.len());
+    }
+
+    blocks
+}
+
+/// Lines outside of code blocks with byte offsets of their starts. Lines keep `\n`
+pub fn lines_outside(source: &str) -> impl Iterator<Item = (usize, &str)> {
+    let blocks = find(source);
+
+    source
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .filter(move |(start, _)| !blocks.iter().any(|block| block.contains(start)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fences() {
+        let source = "```rust\ncode\n```\ntext\n~~~\ncode\n```\n~~~\n";
+
+        assert_eq!(find(source), [0..17, 22..39]);
+    }
+
+    #[test]
+    fn longer_fence() {
+        let source = "````\n```\ncode\n```\n````\ntext\n";
+        let blocks = find(source);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].start, 0);
+        assert_eq!(&source[blocks[0].end..], "text\n");
+    }
+
+    #[test]
+    fn not_fences() {
+        assert!(find("``\ncode\n``\n").is_empty());
+        assert!(find("```inline``` code\n").is_empty());
+        assert!(find("    ```\n    indented\n").is_empty());
+    }
+
+    #[test]
+    fn unclosed() {
+        assert_eq!(find("text\n~~~\ncode").first(), Some(&(5..13)));
+    }
+
+    #[test]
+    fn lines() {
+        let source = "a\n```\nb\n```\nc";
+        let lines: Vec<_> = lines_outside(source).collect();
+
+        assert_eq!(lines, [(0, "a\n"), (12, "c")]);
+    }
+}
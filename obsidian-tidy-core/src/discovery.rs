@@ -0,0 +1,250 @@
+//! Search notes of vault which must be linted
+//!
+//! Hidden files and directories (like `.obsidian`) are always skipped.
+//! Also files are skipped by `.gitignore`, [`IGNORE_FILE`] and exclude globs
+
+use ignore::{
+    WalkBuilder,
+    overrides::{Override, OverrideBuilder},
+};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::{instrument, warn};
+
+/// File with gitignore syntax only for obsidian-tidy
+pub const IGNORE_FILE: &str = ".obtidyignore";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid exclude pattern: {0}")]
+    Glob(#[from] ignore::Error),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscoveryBuilder {
+    root: PathBuf,
+    exclude: Vec<String>,
+    respect_gitignore: bool,
+    no_ignore: bool,
+}
+
+impl DiscoveryBuilder {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            exclude: Vec::new(),
+            respect_gitignore: true,
+            no_ignore: false,
+        }
+    }
+
+    /// Globs relative to root of files which are skipped
+    #[must_use]
+    pub fn exclude(mut self, exclude: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.exclude = exclude.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Skip files ignored by `.gitignore`
+    #[must_use]
+    pub const fn respect_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.respect_gitignore = respect_gitignore;
+        self
+    }
+
+    /// Don't skip files by ignore files and exclude globs
+    #[must_use]
+    pub const fn no_ignore(mut self, no_ignore: bool) -> Self {
+        self.no_ignore = no_ignore;
+        self
+    }
+
+    pub fn build(self) -> Result<Discovery, Error> {
+        let mut overrides = OverrideBuilder::new(&self.root);
+
+        if !self.no_ignore {
+            for glob in &self.exclude {
+                overrides.add(&format!("!{glob}"))?;
+            }
+        }
+
+        Ok(Discovery {
+            overrides: overrides.build()?,
+            root: self.root,
+            respect_gitignore: self.respect_gitignore && !self.no_ignore,
+            use_ignore_file: !self.no_ignore,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Discovery {
+    root: PathBuf,
+    overrides: Override,
+    respect_gitignore: bool,
+    use_ignore_file: bool,
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "md")
+}
+
+impl Discovery {
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn walker(&self, path: &Path) -> WalkBuilder {
+        let mut walker = WalkBuilder::new(path);
+
+        walker
+            .hidden(true)
+            .ignore(false)
+            .git_ignore(self.respect_gitignore)
+            .git_global(self.respect_gitignore)
+            .git_exclude(self.respect_gitignore)
+            .require_git(false)
+            .overrides(self.overrides.clone());
+
+        if self.use_ignore_file {
+            walker.add_custom_ignore_filename(IGNORE_FILE);
+        }
+
+        walker
+    }
+
+    /// Paths of all notes which are not ignored
+    #[instrument(skip(self), fields(root = %self.root.display()))]
+    #[must_use]
+    pub fn notes(&self) -> Vec<PathBuf> {
+        let mut notes: Vec<_> = self
+            .walker(&self.root)
+            .build()
+            .filter_map(|entry| {
+                entry
+                    .inspect_err(|error| warn!("Failed read entry: {error}"))
+                    .ok()
+            })
+            .filter(|entry| entry.file_type().is_some_and(|kind| kind.is_file()))
+            .map(ignore::DirEntry::into_path)
+            .filter(|path| is_markdown(path))
+            .collect();
+
+        notes.sort();
+        notes
+    }
+
+    /// Existing file is a note which isn't ignored
+    #[must_use]
+    pub fn contains(&self, path: &Path) -> bool {
+        if !path.starts_with(&self.root) || !is_markdown(path) {
+            return false;
+        }
+
+        // Ignore rules of directories are applied only while walking,
+        // so walk from root, but only through ancestors of path
+        let target = path.to_path_buf();
+
+        self.walker(&self.root)
+            .filter_entry(move |entry| target.starts_with(entry.path()))
+            .build()
+            .filter_map(Result::ok)
+            .any(|entry| entry.path() == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+    use tracing_test::traced_test;
+
+    fn vault() -> TempDir {
+        let dir = TempDir::new().unwrap();
+
+        for path in [
+            "note.md",
+            "image.png",
+            "templates/template.md",
+            "archive/old.md",
+            "drafts/draft.md",
+            ".obsidian/hidden.md",
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "text").unwrap();
+        }
+
+        fs::write(dir.path().join(".gitignore"), "archive/\n").unwrap();
+        fs::write(dir.path().join(IGNORE_FILE), "drafts/\n").unwrap();
+
+        dir
+    }
+
+    fn relative(dir: &TempDir, notes: Vec<PathBuf>) -> Vec<PathBuf> {
+        notes
+            .into_iter()
+            .map(|path| path.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect()
+    }
+
+    #[test]
+    #[traced_test]
+    fn notes() {
+        let dir = vault();
+        let discovery = DiscoveryBuilder::new(dir.path())
+            .exclude(["templates/**"])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            relative(&dir, discovery.notes()),
+            [PathBuf::from("note.md")]
+        );
+        assert!(discovery.contains(&dir.path().join("note.md")));
+        assert!(!discovery.contains(&dir.path().join("archive/old.md")));
+        assert!(!discovery.contains(&dir.path().join("templates/template.md")));
+    }
+
+    #[test]
+    #[traced_test]
+    fn without_gitignore() {
+        let dir = vault();
+        let discovery = DiscoveryBuilder::new(dir.path())
+            .respect_gitignore(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            relative(&dir, discovery.notes()),
+            [
+                PathBuf::from("archive/old.md"),
+                PathBuf::from("note.md"),
+                PathBuf::from("templates/template.md"),
+            ]
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn no_ignore() {
+        let dir = vault();
+        let discovery = DiscoveryBuilder::new(dir.path())
+            .exclude(["templates/**"])
+            .no_ignore(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(discovery.notes().len(), 4);
+        assert!(!discovery.contains(&dir.path().join(".obsidian/hidden.md")));
+    }
+
+    #[test]
+    #[traced_test]
+    fn invalid_glob() {
+        let result = DiscoveryBuilder::new(".").exclude(["a/**/[b"]).build();
+        assert!(matches!(result, Err(Error::Glob(_))));
+    }
+}
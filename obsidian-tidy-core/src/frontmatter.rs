@@ -0,0 +1,116 @@
+//! YAML frontmatter of note
+
+use serde::de::DeserializeOwned;
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid YAML: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+}
+
+/// Frontmatter between `---` lines at the start of note
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_core::frontmatter::Frontmatter;
+///
+/// let source = "---\ntitle: Note\n---\nText";
+/// let frontmatter = Frontmatter::find(source).unwrap();
+///
+/// assert_eq!(frontmatter.yaml(), "title: Note\n");
+/// assert_eq!(frontmatter.location(), &(0..20));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frontmatter<'a> {
+    yaml: &'a str,
+    yaml_start: usize,
+    location: Range<usize>,
+}
+
+impl<'a> Frontmatter<'a> {
+    /// Find frontmatter in source of note
+    #[must_use]
+    pub fn find(source: &'a str) -> Option<Self> {
+        let mut lines = source.split_inclusive('\n');
+        let first = lines.next()?;
+
+        if first.trim_end_matches(['\r', '\n']) != "---" {
+            return None;
+        }
+
+        let yaml_start = first.len();
+        let mut offset = yaml_start;
+
+        for line in lines {
+            if matches!(line.trim_end_matches(['\r', '\n']), "---" | "...") {
+                return Some(Self {
+                    yaml: &source[yaml_start..offset],
+                    yaml_start,
+                    location: 0..offset + line.len(),
+                });
+            }
+
+            offset += line.len();
+        }
+
+        None
+    }
+
+    /// Text between `---` lines
+    #[must_use]
+    pub const fn yaml(&self) -> &'a str {
+        self.yaml
+    }
+
+    /// Byte offset of [`yaml`](Self::yaml) in source of note
+    #[must_use]
+    pub const fn yaml_start(&self) -> usize {
+        self.yaml_start
+    }
+
+    /// Byte range of frontmatter with `---` lines
+    #[must_use]
+    pub const fn location(&self) -> &Range<usize> {
+        &self.location
+    }
+
+    pub fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(serde_yaml_ng::from_str(self.yaml)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn find() {
+        let frontmatter = Frontmatter::find("---\r\na: 1\r\n---\r\ntext").unwrap();
+
+        assert_eq!(frontmatter.yaml(), "a: 1\r\n");
+        assert_eq!(frontmatter.yaml_start(), 5);
+        assert_eq!(frontmatter.location(), &(0..16));
+    }
+
+    #[test]
+    fn not_found() {
+        assert!(Frontmatter::find("text\n---\na: 1\n---\n").is_none());
+        assert!(Frontmatter::find("---\na: 1\n").is_none());
+        assert!(Frontmatter::find("----\na: 1\n---\n").is_none());
+    }
+
+    #[test]
+    fn parse() {
+        let frontmatter = Frontmatter::find("---\na: 1\nb: text\n---\n").unwrap();
+        let properties: BTreeMap<String, serde_yaml_ng::Value> = frontmatter.parse().unwrap();
+
+        assert_eq!(properties["a"], serde_yaml_ng::Value::from(1));
+        assert_eq!(properties["b"], serde_yaml_ng::Value::from("text"));
+    }
+}
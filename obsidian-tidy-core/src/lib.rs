@@ -2,8 +2,13 @@
 
 #![forbid(clippy::print_stdout)]
 
+pub mod code_blocks;
 pub mod directories;
+pub mod discovery;
+pub mod frontmatter;
+pub mod links;
 pub mod rule;
+pub mod tags;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
@@ -0,0 +1,160 @@
+//! Links of note to other notes
+//!
+//! Wikilinks `[[note#heading|alias]]`, embeds `![[note]]` and markdown links `[alias](note.md)`
+//! outside of code blocks. External links like `https://...` are skipped
+
+use crate::code_blocks;
+use serde::Serialize;
+use std::ops::Range;
+
+/// Link from note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Link<'a> {
+    /// Target as written, like `note#heading`
+    pub target: &'a str,
+
+    /// Text of link which is shown instead of target
+    pub alias: Option<&'a str>,
+
+    /// Link starts with `!`
+    pub embed: bool,
+
+    /// Byte range of link in source of note
+    pub location: Range<usize>,
+}
+
+impl<'a> Link<'a> {
+    /// Target without heading or block, like `note` for `note#^block`.
+    /// Empty for link to heading of the same note
+    #[must_use]
+    pub fn note(&self) -> &'a str {
+        self.target.split('#').next().unwrap_or_default().trim()
+    }
+}
+
+/// Target of markdown link which points to note or attachment of vault
+fn markdown_target(target: &str) -> Option<&str> {
+    let target = target.trim();
+    let target = target
+        .strip_prefix('<')
+        .and_then(|target| target.strip_suffix('>'))
+        .or_else(|| target.split_once(" \"").map(|(target, _title)| target))
+        .unwrap_or(target);
+
+    let external = target.contains("://") || target.starts_with("mailto:");
+    (!target.is_empty() && !external).then_some(target)
+}
+
+fn from_line<'a>(line: &'a str, offset: usize, links: &mut Vec<Link<'a>>) {
+    let mut rest = 0;
+
+    while let Some(start) = line[rest..].find('[').map(|index| index + rest) {
+        let embed = line[..start].ends_with('!');
+        let link_start = offset + start - usize::from(embed);
+
+        if line[start..].starts_with("[[") {
+            let inner_start = start + 2;
+            let Some(length) = line[inner_start..].find("]]") else {
+                return;
+            };
+
+            let inner = &line[inner_start..inner_start + length];
+            let end = inner_start + length + 2;
+            let (target, alias) = inner
+                .split_once('|')
+                .map_or((inner, None), |(target, alias)| (target, Some(alias)));
+
+            if !target.trim().is_empty() {
+                links.push(Link {
+                    target: target.trim(),
+                    alias,
+                    embed,
+                    location: link_start..offset + end,
+                });
+            }
+
+            rest = end;
+            continue;
+        }
+
+        let Some(close) = line[start + 1..].find(']').map(|index| index + start + 1) else {
+            return;
+        };
+
+        let target_start = close + 2;
+        let length = line[close + 1..]
+            .starts_with('(')
+            .then(|| line[target_start..].find(')'))
+            .flatten();
+
+        let Some(length) = length else {
+            rest = start + 1;
+            continue;
+        };
+
+        let end = target_start + length + 1;
+
+        if let Some(target) = markdown_target(&line[target_start..target_start + length]) {
+            let alias = &line[start + 1..close];
+
+            links.push(Link {
+                target,
+                alias: (!alias.is_empty()).then_some(alias),
+                embed,
+                location: link_start..offset + end,
+            });
+        }
+
+        rest = end;
+    }
+}
+
+/// Find links in source of note
+#[must_use]
+pub fn find(source: &str) -> Vec<Link<'_>> {
+    let mut links = Vec::new();
+
+    for (start, line) in code_blocks::lines_outside(source) {
+        from_line(line, start, &mut links);
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_links() {
+        let source = "See [[Note#Heading|alias]] and ![[image.png]].\n\
+                      [Docs](folder/docs.md \"Title\") [site](https://example.com) [[#Local]]\n";
+
+        let links = find(source);
+        let targets: Vec<_> = links.iter().map(|link| link.target).collect();
+
+        assert_eq!(
+            targets,
+            ["Note#Heading", "image.png", "folder/docs.md", "#Local"]
+        );
+
+        assert_eq!(links[0].note(), "Note");
+        assert_eq!(links[0].alias, Some("alias"));
+        assert_eq!(&source[links[0].location.clone()], "[[Note#Heading|alias]]");
+
+        assert!(links[1].embed);
+        assert_eq!(&source[links[1].location.clone()], "![[image.png]]");
+
+        assert_eq!(links[2].alias, Some("Docs"));
+        assert_eq!(links[3].note(), "");
+    }
+
+    #[test]
+    fn skip_code_blocks() {
+        let source = "```\n[[Not a link]]\n```\n[[Link]]\n~~~\n[[Not a link]]\n~~~\n";
+        let links = find(source);
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].location, 23..31);
+    }
+}
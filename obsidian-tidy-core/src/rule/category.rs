@@ -12,6 +12,28 @@ pub enum Category {
     Other,
 }
 
+impl Category {
+    pub const ALL: [Self; 5] = [
+        Self::Yaml,
+        Self::Heading,
+        Self::Content,
+        Self::Spacing,
+        Self::Other,
+    ];
+
+    /// Name of category in config
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Yaml => "yaml",
+            Self::Heading => "heading",
+            Self::Content => "content",
+            Self::Spacing => "spacing",
+            Self::Other => "other",
+        }
+    }
+}
+
 impl Display for Category {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
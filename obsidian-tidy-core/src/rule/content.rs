@@ -1,10 +1,31 @@
-use crate::Vault;
-use obsidian_parser::prelude::*;
-use std::path::Path;
+use crate::{Note, NoteError, Vault, frontmatter::Frontmatter};
+use obsidian_parser::prelude::Note as _;
+use obsidian_parser::{note::NoteDefault, prelude::*};
+use rayon::prelude::*;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use tracing::{debug, instrument, warn};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Error from parser: {0}")]
+    Parser(#[from] NoteError),
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct Content {
-    pub vault: Vault,
+    pub vault: Arc<Vault>,
+
+    /// Sources of notes which are not written to disk yet
+    sources: HashMap<PathBuf, Arc<str>>,
 }
 
 impl Content {
@@ -16,12 +37,119 @@ impl Content {
             .filter_map(Result::ok)
             .build_vault(&options);
 
-        Self { vault }
+        Self::from(vault)
+    }
+
+    /// Build content from already parsed notes of vault in `path`
+    pub fn from_notes(path: impl AsRef<Path>, notes: impl IntoIterator<Item = Note>) -> Self {
+        let options = VaultOptions::new(path);
+        let vault = notes.into_iter().build_vault(&options);
+
+        Self::from(vault)
+    }
+
+    /// Build content from notes in `files`. Files which can't be parsed are skipped
+    pub fn from_files(path: impl AsRef<Path>, files: &[PathBuf]) -> Self {
+        let notes: Vec<_> = files
+            .par_iter()
+            .filter_map(|file| {
+                let source = std::fs::read_to_string(file)
+                    .inspect_err(|error| warn!("Failed read `{}`: {error}", file.display()))
+                    .ok()?;
+
+                parse_note(&source, file)
+                    .inspect_err(|error| warn!("Failed parse `{}`: {error}", file.display()))
+                    .ok()
+            })
+            .collect();
+
+        Self::from_notes(path, notes)
+    }
+
+    /// Use `source` instead of file on disk for note with `path`
+    #[must_use]
+    pub fn with_source(mut self, path: impl Into<PathBuf>, source: impl Into<Arc<str>>) -> Self {
+        self.sources.insert(path.into(), source.into());
+        self
+    }
+
+    /// Full text of note with frontmatter.
+    /// Locations of [`Violation`](super::Violation) are byte offsets in it
+    #[instrument(skip_all, err)]
+    pub fn source<'a>(&'a self, note: &'a Note) -> Result<Cow<'a, str>, Error> {
+        let Some(path) = note.path() else {
+            return Ok(note.content()?);
+        };
+
+        if let Some(source) = self.sources.get(path.as_ref()) {
+            return Ok(Cow::Borrowed(source));
+        }
+
+        Ok(Cow::Owned(std::fs::read_to_string(path)?))
     }
 }
 
 impl From<Vault> for Content {
     fn from(vault: Vault) -> Self {
-        Self { vault }
+        Self {
+            vault: Arc::new(vault),
+            sources: HashMap::new(),
+        }
+    }
+}
+
+/// Parse note from source which isn't written to `path` yet.
+/// Note with invalid YAML in frontmatter is kept without properties, so rules can report it
+pub fn parse_note(source: &str, path: impl Into<PathBuf>) -> Result<Note, NoteError> {
+    let mut note = match Note::from_string_default(source) {
+        Ok(note) => note,
+        Err(NoteError::Yaml(error)) => {
+            debug!("Invalid YAML in frontmatter: {error}");
+
+            let body = Frontmatter::find(source)
+                .map_or(source, |frontmatter| &source[frontmatter.location().end..]);
+
+            Note::from_string_default(body).unwrap_or_default()
+        }
+        Err(error) => return Err(error),
+    };
+
+    note.set_path(Some(path.into()));
+
+    Ok(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn source_from_overlay() {
+        let note = parse_note("New text", "note.md").unwrap();
+        let content = Content::default().with_source("note.md", "New text");
+
+        assert_eq!(content.source(&note).unwrap(), "New text");
+    }
+
+    #[test]
+    #[traced_test]
+    fn invalid_frontmatter() {
+        let source = "---\ntitle: a: b\n---\nText";
+        let note = parse_note(source, "note.md").unwrap();
+        let content = Content::default().with_source("note.md", source);
+
+        assert!(note.properties().unwrap().is_none());
+        assert_eq!(content.source(&note).unwrap(), source);
+    }
+
+    #[test]
+    #[traced_test]
+    fn source_without_path() {
+        let note = Note::from_string_default("Text").unwrap();
+        let content = Content::default();
+
+        assert_eq!(content.source(&note).unwrap(), "Text");
     }
 }
@@ -0,0 +1,79 @@
+//! Long-form documentation of rule
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Documentation of rule for `explain` command and reference of rules
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_core::rule::Docs;
+///
+/// let docs = Docs::new("Empty notes clutter search and graph")
+///     .bad("---\ntags: [idea]\n---\n")
+///     .good("---\ntags: [idea]\n---\nIdea about notes\n")
+///     .option("min-words", "Note with fewer words is empty");
+///
+/// assert_eq!(docs.rationale(), "Empty notes clutter search and graph");
+/// assert_eq!(docs.options()["min-words"], "Note with fewer words is empty");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Docs {
+    rationale: String,
+    bad: Option<String>,
+    good: Option<String>,
+    options: BTreeMap<String, String>,
+}
+
+impl Docs {
+    /// `rationale` explains why rule exists
+    #[must_use]
+    pub fn new(rationale: impl Into<String>) -> Self {
+        Self {
+            rationale: rationale.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Markdown which violates rule
+    #[must_use]
+    pub fn bad(mut self, markdown: impl Into<String>) -> Self {
+        self.bad = Some(markdown.into());
+        self
+    }
+
+    /// Markdown which follows rule
+    #[must_use]
+    pub fn good(mut self, markdown: impl Into<String>) -> Self {
+        self.good = Some(markdown.into());
+        self
+    }
+
+    /// Description of option `name` from [`Rule::default_options`](super::Rule::default_options)
+    #[must_use]
+    pub fn option(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.options.insert(name.into(), description.into());
+        self
+    }
+
+    #[must_use]
+    pub fn rationale(&self) -> &str {
+        &self.rationale
+    }
+
+    #[must_use]
+    pub fn bad_example(&self) -> Option<&str> {
+        self.bad.as_deref()
+    }
+
+    #[must_use]
+    pub fn good_example(&self) -> Option<&str> {
+        self.good.as_deref()
+    }
+
+    /// Descriptions of options by name
+    #[must_use]
+    pub const fn options(&self) -> &BTreeMap<String, String> {
+        &self.options
+    }
+}
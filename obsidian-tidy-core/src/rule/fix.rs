@@ -0,0 +1,253 @@
+//! Fixes for violations
+
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, ops::Range};
+use tracing::{instrument, trace};
+
+/// Replace byte range of note source with text
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Edit {
+    range: Range<usize>,
+    replacement: String,
+}
+
+impl Edit {
+    #[must_use]
+    pub fn replace(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn insert(offset: usize, text: impl Into<String>) -> Self {
+        Self::replace(offset..offset, text)
+    }
+
+    #[must_use]
+    pub fn delete(range: Range<usize>) -> Self {
+        Self::replace(range, String::new())
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// Edit can be applied to source
+    const fn is_valid_for(&self, source: &str) -> bool {
+        self.range.start <= self.range.end
+            && self.range.end <= source.len()
+            && source.is_char_boundary(self.range.start)
+            && source.is_char_boundary(self.range.end)
+    }
+
+    const fn overlaps(&self, other: &Self) -> bool {
+        let (a, b) = (&self.range, &other.range);
+        (a.start < b.end && b.start < a.end) || a.start == b.start
+    }
+}
+
+/// Is fix safe to apply without review
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Applicability {
+    /// Fix doesn't change meaning of note
+    #[default]
+    Safe,
+
+    /// Fix may change meaning of note. Applied only by request
+    Unsafe,
+}
+
+impl Display for Applicability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Applicability::Safe => write!(f, "safe"),
+            Applicability::Unsafe => write!(f, "unsafe"),
+        }
+    }
+}
+
+/// Set of edits which fixes a violation. Edits are applied all together or not applied
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fix {
+    applicability: Applicability,
+    edits: Vec<Edit>,
+}
+
+impl Fix {
+    #[must_use]
+    pub fn new(applicability: Applicability, edits: impl IntoIterator<Item = Edit>) -> Self {
+        let mut edits: Vec<_> = edits.into_iter().collect();
+        edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+        Self {
+            applicability,
+            edits,
+        }
+    }
+
+    #[must_use]
+    pub fn safe(edits: impl IntoIterator<Item = Edit>) -> Self {
+        Self::new(Applicability::Safe, edits)
+    }
+
+    #[must_use]
+    pub fn unsafe_edits(edits: impl IntoIterator<Item = Edit>) -> Self {
+        Self::new(Applicability::Unsafe, edits)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn applicability(&self) -> Applicability {
+        self.applicability
+    }
+
+    /// Edits sorted by position
+    #[inline]
+    #[must_use]
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    fn is_valid_for(&self, source: &str) -> bool {
+        self.edits.iter().all(|edit| edit.is_valid_for(source))
+            && self
+                .edits
+                .windows(2)
+                .all(|edits| !edits[0].overlaps(&edits[1]))
+    }
+}
+
+/// Apply fixes to source.
+///
+/// Fixes which overlap with previous fixes or are out of source are skipped,
+/// so source should be checked again after applying.
+/// Returns new source and count of applied fixes
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_core::rule::fix::{Edit, Fix, apply_fixes};
+///
+/// let fixes = [
+///     Fix::safe([Edit::replace(0..5, "Hi")]),
+///     Fix::safe([Edit::replace(2..4, "skipped")]), // Overlaps with first fix
+///     Fix::safe([Edit::insert(11, "!")]),
+/// ];
+///
+/// let (fixed, applied) = apply_fixes("Hello world", &fixes);
+///
+/// assert_eq!(fixed, "Hi world!");
+/// assert_eq!(applied, 2);
+/// ```
+#[instrument(skip_all)]
+pub fn apply_fixes<'a>(source: &str, fixes: impl IntoIterator<Item = &'a Fix>) -> (String, usize) {
+    let mut fixes: Vec<_> = fixes
+        .into_iter()
+        .filter(|fix| !fix.edits.is_empty() && fix.is_valid_for(source))
+        .collect();
+
+    fixes.sort_by_key(|fix| (fix.edits[0].range.start, fix.edits[0].range.end));
+
+    let mut accepted: Vec<&Edit> = Vec::new();
+    let mut applied = 0;
+
+    for fix in fixes {
+        let conflicts = fix
+            .edits
+            .iter()
+            .any(|edit| accepted.iter().any(|other| edit.overlaps(other)));
+
+        if conflicts {
+            trace!("Skip fix which overlaps with another fix");
+            continue;
+        }
+
+        accepted.extend(&fix.edits);
+        applied += 1;
+    }
+
+    accepted.sort_by_key(|edit| edit.range.start);
+
+    let mut result = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for edit in accepted {
+        result.push_str(&source[last..edit.range.start]);
+        result.push_str(&edit.replacement);
+        last = edit.range.end;
+    }
+
+    result.push_str(&source[last..]);
+
+    (result, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_one_fix() {
+        let fix = Fix::safe([Edit::replace(6..11, "vault")]);
+        let (fixed, applied) = apply_fixes("Hello world", [&fix]);
+
+        assert_eq!(fixed, "Hello vault");
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    fn apply_fix_with_many_edits() {
+        let fix = Fix::safe([Edit::insert(5, "]]"), Edit::insert(0, "[[")]);
+        let (fixed, applied) = apply_fixes("Hello world", [&fix]);
+
+        assert_eq!(fixed, "[[Hello]] world");
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    fn skip_overlapping() {
+        let first = Fix::safe([Edit::delete(0..6)]);
+        let second = Fix::safe([Edit::replace(3..8, "")]);
+        let (fixed, applied) = apply_fixes("Hello world", [&second, &first]);
+
+        assert_eq!(fixed, "world");
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    fn skip_same_insert_position() {
+        let first = Fix::safe([Edit::insert(0, "a")]);
+        let second = Fix::safe([Edit::insert(0, "b")]);
+        let (fixed, applied) = apply_fixes("text", [&first, &second]);
+
+        assert_eq!(fixed, "atext");
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    fn skip_invalid() {
+        let out_of_source = Fix::safe([Edit::delete(0..100)]);
+        let inside_char = Fix::safe([Edit::delete(0..1)]);
+        let (fixed, applied) = apply_fixes("ж", [&out_of_source, &inside_char]);
+
+        assert_eq!(fixed, "ж");
+        assert_eq!(applied, 0);
+    }
+
+    #[test]
+    fn applicability_order() {
+        assert!(Applicability::Safe < Applicability::Unsafe);
+    }
+}
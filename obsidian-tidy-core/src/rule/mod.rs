@@ -2,19 +2,30 @@
 
 pub mod category;
 pub mod content;
+pub mod docs;
+pub mod fix;
+pub mod options;
+pub mod path_filter;
 pub mod rules;
+pub mod severity;
 pub mod shared_error_rule;
 pub mod smart_pointer;
 pub mod toggleable_rule;
 pub mod violation;
 
 use crate::Note;
+use options::Error as OptionsError;
 use std::{fmt::Debug, sync::Arc};
 
 pub use category::Category;
 pub use content::Content;
+pub use docs::Docs;
+pub use fix::{Applicability, Edit, Fix};
+pub use options::Options;
+pub use path_filter::PathFilter;
 pub use rules::Rules;
 pub use rules::serde::{InnerRules, RulesSeed};
+pub use severity::Severity;
 pub use shared_error_rule::SharedErrorRule;
 pub use toggleable_rule::ToggleableRule;
 pub use violation::Violation;
@@ -36,6 +47,43 @@ pub trait Rule: Send + Sync {
     /// Category rule
     fn category(&self) -> Category;
 
+    /// Severity of violations if config doesn't override it
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Which fixes rule can attach to violations. `None` if rule doesn't fix anything
+    fn fixable(&self) -> Option<Applicability> {
+        None
+    }
+
+    /// Rule reads other notes or links graph of vault,
+    /// so its result for note may change when another note changes
+    fn depends_on_vault(&self) -> bool {
+        false
+    }
+
+    /// Long-form documentation of rule. Empty by default
+    fn docs(&self) -> Docs {
+        Docs::default()
+    }
+
+    /// Options of rule with default values. Empty if rule has no options
+    fn default_options(&self) -> Options {
+        Options::new()
+    }
+
+    /// The same rule with options from config. Missing options must get default values.
+    /// `None` if options don't change rule.
+    /// By default any option is unknown
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        options::reject(options)?;
+        Ok(None)
+    }
+
     /// Run check by this rule
     fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error>;
 }
@@ -49,6 +97,10 @@ where
             .field("name", &self.name())
             .field("description", &self.description())
             .field("category", &self.category())
+            .field("default_severity", &self.default_severity())
+            .field("fixable", &self.fixable())
+            .field("depends_on_vault", &self.depends_on_vault())
+            .field("default_options", &self.default_options())
             .finish()
     }
 }
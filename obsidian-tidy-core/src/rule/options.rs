@@ -0,0 +1,113 @@
+//! Options of rules from config
+//!
+//! Rule declares its options by a struct with `#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]`
+//! and converts it by [`to_options`] and [`from_options`]
+
+use serde::{Serialize, de::DeserializeOwned};
+use std::mem::discriminant;
+use thiserror::Error;
+
+/// Options of rule. Keys are in kebab-case
+pub type Options = toml::Table;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Unknown options: {}", .0.join(", "))]
+    Unknown(Vec<String>),
+
+    #[error("Invalid options: {0}")]
+    Invalid(#[from] toml::de::Error),
+}
+
+/// Options from typed options of rule
+#[must_use]
+pub fn to_options<T>(options: &T) -> Options
+where
+    T: Serialize,
+{
+    Options::try_from(options).unwrap_or_default()
+}
+
+/// Typed options of rule from config
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_core::rule::options::{Options, from_options};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// #[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+/// struct LineLength {
+///     max_length: usize,
+/// }
+///
+/// impl Default for LineLength {
+///     fn default() -> Self {
+///         Self { max_length: 80 }
+///     }
+/// }
+///
+/// let options: Options = toml::from_str("max-length = 100").unwrap();
+/// assert_eq!(from_options::<LineLength>(&options).unwrap().max_length, 100);
+/// assert_eq!(from_options::<LineLength>(&Options::new()).unwrap().max_length, 80);
+/// assert!(from_options::<LineLength>(&toml::from_str("other = 1").unwrap()).is_err());
+/// ```
+pub fn from_options<T>(options: &Options) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    Ok(toml::Value::Table(options.clone()).try_into()?)
+}
+
+/// Options of rule which has no options must be empty
+pub fn reject(options: &Options) -> Result<(), Error> {
+    if options.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Unknown(options.keys().cloned().collect()))
+    }
+}
+
+/// Options of rule which declares them by default values, like custom rules.
+/// Options must be in `defaults` and have the same type. Missing options get default values
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_core::rule::options::{Options, merge};
+///
+/// let defaults: Options = toml::from_str("max-length = 80\nstrict = false").unwrap();
+///
+/// let merged = merge(&defaults, &toml::from_str("max-length = 100").unwrap()).unwrap();
+/// assert_eq!(merged["max-length"].as_integer(), Some(100));
+/// assert_eq!(merged["strict"].as_bool(), Some(false));
+///
+/// assert!(merge(&defaults, &toml::from_str("other = 1").unwrap()).is_err());
+/// assert!(merge(&defaults, &toml::from_str("strict = 'yes'").unwrap()).is_err());
+/// ```
+pub fn merge(defaults: &Options, options: &Options) -> Result<Options, Error> {
+    let unknown: Vec<_> = options
+        .keys()
+        .filter(|option| !defaults.contains_key(*option))
+        .cloned()
+        .collect();
+
+    if !unknown.is_empty() {
+        return Err(Error::Unknown(unknown));
+    }
+
+    for (option, value) in options {
+        let default = &defaults[option];
+
+        if discriminant(value) != discriminant(default) {
+            return Err(Error::Invalid(serde::de::Error::custom(format!(
+                "option `{option}` must be {}",
+                default.type_str()
+            ))));
+        }
+    }
+
+    let mut merged = defaults.clone();
+    merged.extend(options.clone());
+
+    Ok(merged)
+}
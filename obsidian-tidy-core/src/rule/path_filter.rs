@@ -0,0 +1,139 @@
+//! Globs which select notes checked by rule
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Invalid glob `{glob}`: {source}")]
+    Glob {
+        glob: String,
+        source: globset::Error,
+    },
+}
+
+fn glob_set(globs: &[String]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for glob in globs {
+        builder.add(Glob::new(glob).map_err(|source| Error::Glob {
+            glob: glob.clone(),
+            source,
+        })?);
+    }
+
+    builder.build().map_err(|source| Error::Glob {
+        glob: globs.join(", "),
+        source,
+    })
+}
+
+/// Include and exclude globs relative to the vault.
+/// Empty `include` means all notes
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_core::rule::PathFilter;
+/// use std::path::Path;
+///
+/// let filter = PathFilter::new(["projects/**"], ["projects/archive/**"]).unwrap();
+///
+/// assert!(filter.matches(Path::new("projects/todo.md")));
+/// assert!(!filter.matches(Path::new("projects/archive/old.md")));
+/// assert!(!filter.matches(Path::new("daily/2024-01-01.md")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    include_set: GlobSet,
+    exclude_set: GlobSet,
+}
+
+impl PathFilter {
+    pub fn new(
+        include: impl IntoIterator<Item = impl Into<String>>,
+        exclude: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, Error> {
+        let include: Vec<String> = include.into_iter().map(Into::into).collect();
+        let exclude: Vec<String> = exclude.into_iter().map(Into::into).collect();
+
+        Ok(Self {
+            include_set: glob_set(&include)?,
+            exclude_set: glob_set(&exclude)?,
+            include,
+            exclude,
+        })
+    }
+
+    #[must_use]
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    #[must_use]
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    /// Filter doesn't skip any note
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Note with `path` relative to the vault must be checked
+    #[must_use]
+    pub fn matches(&self, path: &Path) -> bool {
+        (self.include.is_empty() || self.include_set.is_match(path))
+            && !self.exclude_set.is_match(path)
+    }
+}
+
+impl Default for PathFilter {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_set: GlobSet::empty(),
+            exclude_set: GlobSet::empty(),
+        }
+    }
+}
+
+impl PartialEq for PathFilter {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.include, &self.exclude) == (&other.include, &other.exclude)
+    }
+}
+
+impl Eq for PathFilter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let filter = PathFilter::default();
+
+        assert!(filter.is_empty());
+        assert!(filter.matches(Path::new("note.md")));
+        assert!(filter.matches(Path::new("daily/note.md")));
+    }
+
+    #[test]
+    fn exclude() {
+        let filter = PathFilter::new(Vec::<String>::new(), ["daily/**"]).unwrap();
+
+        assert!(filter.matches(Path::new("note.md")));
+        assert!(!filter.matches(Path::new("daily/note.md")));
+    }
+
+    #[test]
+    fn invalid_glob() {
+        let result = PathFilter::new(["a/[b"], ["c"]);
+        assert!(matches!(result, Err(Error::Glob { glob, .. }) if glob == "a/[b"));
+    }
+}
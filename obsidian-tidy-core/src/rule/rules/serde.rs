@@ -1,5 +1,5 @@
 use super::Rules;
-use crate::rule::{Category, Rule, ToggleableRule};
+use crate::rule::{Category, Options, PathFilter, Rule, Severity, ToggleableRule};
 use ::serde::{Deserialize, Serialize, Serializer};
 use serde::{Deserializer, de::DeserializeSeed};
 use std::{
@@ -9,8 +9,28 @@ use std::{
 use tracing::{instrument, trace};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct RuleConfig {
     pub enable: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+
+    /// Globs of notes which are checked. Empty means all notes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Globs of notes which are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// Tags of notes which are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_tags: Vec<String>,
+
+    /// Options of rule. See [`Rule::default_options`]
+    #[serde(flatten)]
+    pub options: Options,
 }
 
 type RuleName = String;
@@ -22,12 +42,10 @@ pub struct InnerRules(BTreeMap<Category, CategoryRules>);
 
 impl InnerRules {
     #[instrument]
-    fn add_rule(&mut self, name: RuleName, category: Category, enable: bool) {
+    fn add_rule(&mut self, name: RuleName, category: Category, config: RuleConfig) {
         trace!("Add rule");
 
-        self.entry(category)
-            .or_default()
-            .insert(name, RuleConfig { enable });
+        self.entry(category).or_default().insert(name, config);
     }
 }
 
@@ -56,7 +74,16 @@ where
         let mut rules = InnerRules::default();
 
         for rule in &self.0 {
-            rules.add_rule(rule.name().to_string(), rule.category(), rule.is_enabled());
+            let config = RuleConfig {
+                enable: rule.is_enabled(),
+                severity: rule.severity_override(),
+                include: rule.paths().include().to_vec(),
+                exclude: rule.paths().exclude().to_vec(),
+                ignore_tags: rule.ignore_tags().to_vec(),
+                options: rule.merged_options(),
+            };
+
+            rules.add_rule(rule.name().to_string(), rule.category(), config);
         }
 
         serializer.serialize_newtype_struct("rules", &rules)
@@ -103,11 +130,19 @@ where
                     .available_rules
                     .iter()
                     .find(|rule| rule.name() == name)
-                    .ok_or(serde::de::Error::missing_field(
-                        "not found rule from `available_rules`",
-                    ))?;
+                    .ok_or_else(|| serde::de::Error::custom(format!("Unknown rule `{name}`")))?;
 
-                vec_rules.push(ToggleableRule::new(rule.clone(), config.enable));
+                let paths = PathFilter::new(&config.include, &config.exclude)
+                    .map_err(serde::de::Error::custom)?;
+
+                let rule = ToggleableRule::new(rule.clone(), config.enable)
+                    .with_severity(config.severity)
+                    .with_paths(paths)
+                    .with_ignore_tags(config.ignore_tags.clone())
+                    .configure(config.options.clone())
+                    .map_err(|error| serde::de::Error::custom(format!("Rule `{name}`: {error}")))?;
+
+                vec_rules.push(rule);
             }
         }
 
@@ -120,7 +155,7 @@ where
 mod tests {
     use super::*;
     use crate::rule::ToggleableRule;
-    use crate::test_utils::TestRule;
+    use crate::test_utils::{MessageRule, TestRule};
     use std::sync::Arc;
 
     #[test]
@@ -168,7 +203,38 @@ enable = false
     }
 
     #[test]
-    #[should_panic(expected = "not found rule")]
+    fn severity() {
+        let rule1 = Arc::new(TestRule::new("rule1", "", Category::Content, []));
+        let rule2 = Arc::new(TestRule::new("rule2", "", Category::Spacing, []));
+
+        let toggleable_rule1 =
+            ToggleableRule::new(rule1.clone(), true).with_severity(Some(Severity::Error));
+        let toggleable_rule2 = ToggleableRule::new(rule2.clone(), true);
+
+        let rules = Rules::new(vec![toggleable_rule1, toggleable_rule2]).unwrap();
+        let toml = toml::to_string(&rules).unwrap();
+
+        assert_eq!(
+            toml,
+            r#"[content.rule1]
+enable = true
+severity = "error"
+
+[spacing.rule2]
+enable = true
+"#
+        );
+
+        let available_rules = vec![rule1, rule2];
+        let rules_deserialized = RulesSeed::new(&available_rules)
+            .deserialize(toml::Deserializer::parse(&toml).unwrap())
+            .unwrap();
+
+        assert_eq!(rules, rules_deserialized);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown rule `rule2`")]
     fn deserialize_with_not_found_rule() {
         let rule1 = Arc::new(TestRule::new("rule1", "", Category::Content, []));
         let rule2 = Arc::new(TestRule::new("rule2", "", Category::Spacing, []));
@@ -186,4 +252,79 @@ enable = false
 
         assert_eq!(rules, rules_deserialized);
     }
+
+    #[test]
+    fn paths() {
+        let rule1 = Arc::new(TestRule::new("rule1", "", Category::Content, []));
+        let paths = PathFilter::new(["projects/**"], ["daily/**"]).unwrap();
+        let toggleable_rule1 = ToggleableRule::new(rule1.clone(), true)
+            .with_paths(paths)
+            .with_ignore_tags(vec!["draft".to_string()]);
+
+        let rules = Rules::new(vec![toggleable_rule1]).unwrap();
+        let toml = toml::to_string(&rules).unwrap();
+
+        assert_eq!(
+            toml,
+            r#"[content.rule1]
+enable = true
+include = ["projects/**"]
+exclude = ["daily/**"]
+ignore-tags = ["draft"]
+"#
+        );
+
+        let available_rules = vec![rule1];
+        let rules_deserialized = RulesSeed::new(&available_rules)
+            .deserialize(toml::Deserializer::parse(&toml).unwrap())
+            .unwrap();
+
+        assert_eq!(rules, rules_deserialized);
+    }
+
+    #[test]
+    fn invalid_glob() {
+        let rule1 = Arc::new(TestRule::new("rule1", "", Category::Content, []));
+        let toml = "[content.rule1]\nenable = true\nexclude = [\"a/[b\"]\n";
+
+        let available_rules = vec![rule1];
+        let error = RulesSeed::new(&available_rules)
+            .deserialize(toml::Deserializer::parse(toml).unwrap())
+            .unwrap_err();
+
+        assert!(error.to_string().contains("Invalid glob `a/[b`"));
+    }
+
+    #[test]
+    fn options() {
+        let rule = Arc::new(MessageRule::default());
+        let rules = Rules::new(vec![ToggleableRule::new(rule.clone(), true)]).unwrap();
+        let toml = toml::to_string(&rules).unwrap();
+
+        assert_eq!(
+            toml,
+            r#"[other.message-rule]
+enable = true
+violation-message = "Default message"
+"#
+        );
+
+        let available_rules = vec![rule];
+        let toml = "[other.message-rule]\nenable = true\nviolation-message = \"Custom\"\n";
+        let rules_deserialized = RulesSeed::new(&available_rules)
+            .deserialize(toml::Deserializer::parse(toml).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            rules_deserialized["message-rule"].options()["violation-message"].as_str(),
+            Some("Custom")
+        );
+
+        let toml = "[other.message-rule]\nenable = true\nunknown = 1\n";
+        let error = RulesSeed::new(&available_rules)
+            .deserialize(toml::Deserializer::parse(toml).unwrap())
+            .unwrap_err();
+
+        assert!(error.to_string().contains("Rule `message-rule`"));
+    }
 }
@@ -0,0 +1,83 @@
+//! Severity of violations
+
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+/// How important is a violation.
+/// Ordered from the least important to the most important
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Hint,
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("Unknown severity `{0}`. Expected one of: error, warning, info, hint")]
+pub struct ParseSeverityError(String);
+
+impl Severity {
+    pub const ALL: [Self; 4] = [Self::Error, Self::Warning, Self::Info, Self::Hint];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hint => "hint",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = ParseSeverityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseSeverityError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        assert!(Severity::Error > Severity::Warning);
+        assert!(Severity::Warning > Severity::Info);
+        assert!(Severity::Info > Severity::Hint);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("error".parse(), Ok(Severity::Error));
+        assert_eq!("Warning".parse(), Ok(Severity::Warning));
+        assert_eq!(
+            "fatal".parse::<Severity>(),
+            Err(ParseSeverityError("fatal".to_string()))
+        );
+    }
+
+    #[test]
+    fn display() {
+        for severity in Severity::ALL {
+            assert_eq!(severity.to_string().parse(), Ok(severity));
+        }
+    }
+}
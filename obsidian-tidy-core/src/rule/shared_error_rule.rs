@@ -1,4 +1,7 @@
-use super::{Category, Content, Rule, Violation};
+use super::{
+    Applicability, Category, Content, Docs, Options, Rule, Severity, Violation,
+    options::Error as OptionsError,
+};
 use crate::{Note, rule::DynRule};
 use std::{ops::Deref, sync::Arc};
 
@@ -36,6 +39,39 @@ impl Rule for SharedErrorRule {
         self.inner.category()
     }
 
+    #[inline]
+    fn default_severity(&self) -> Severity {
+        self.inner.default_severity()
+    }
+
+    #[inline]
+    fn fixable(&self) -> Option<Applicability> {
+        self.inner.fixable()
+    }
+
+    #[inline]
+    fn depends_on_vault(&self) -> bool {
+        self.inner.depends_on_vault()
+    }
+
+    #[inline]
+    fn docs(&self) -> Docs {
+        self.inner.docs()
+    }
+
+    #[inline]
+    fn default_options(&self) -> Options {
+        self.inner.default_options()
+    }
+
+    #[inline]
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        self.inner.with_options(options)
+    }
+
     #[inline]
     fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
         self.inner.check(content, note)
@@ -92,6 +128,39 @@ where
         self.0.category()
     }
 
+    #[inline]
+    fn default_severity(&self) -> Severity {
+        self.0.default_severity()
+    }
+
+    #[inline]
+    fn fixable(&self) -> Option<Applicability> {
+        self.0.fixable()
+    }
+
+    #[inline]
+    fn depends_on_vault(&self) -> bool {
+        self.0.depends_on_vault()
+    }
+
+    #[inline]
+    fn docs(&self) -> Docs {
+        self.0.docs()
+    }
+
+    #[inline]
+    fn default_options(&self) -> Options {
+        self.0.default_options()
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        let rule = self.0.with_options(options)?;
+        Ok(rule.map(|rule| Arc::new(ErasingRule(rule)) as DynRule<Self::Error>))
+    }
+
     fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
         self.0
             .check(content, note)
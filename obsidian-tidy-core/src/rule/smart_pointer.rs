@@ -2,12 +2,15 @@
 
 use crate::Note;
 
-use super::{Category, Content, Rule, Violation};
+use super::{
+    Applicability, Category, Content, Docs, DynRule, Options, Rule, Severity, Violation,
+    options::Error as OptionsError,
+};
 use std::sync::Arc;
 
 impl<L> Rule for Box<L>
 where
-    L: Rule,
+    L: Rule + ?Sized,
 {
     type Error = L::Error;
 
@@ -23,6 +26,33 @@ where
         self.as_ref().category()
     }
 
+    fn default_severity(&self) -> Severity {
+        self.as_ref().default_severity()
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        self.as_ref().fixable()
+    }
+
+    fn depends_on_vault(&self) -> bool {
+        self.as_ref().depends_on_vault()
+    }
+
+    fn docs(&self) -> Docs {
+        self.as_ref().docs()
+    }
+
+    fn default_options(&self) -> Options {
+        self.as_ref().default_options()
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        self.as_ref().with_options(options)
+    }
+
     fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
         self.as_ref().check(content, note)
     }
@@ -30,7 +60,7 @@ where
 
 impl<L> Rule for Arc<L>
 where
-    L: Rule,
+    L: Rule + ?Sized,
 {
     type Error = L::Error;
 
@@ -46,6 +76,33 @@ where
         self.as_ref().category()
     }
 
+    fn default_severity(&self) -> Severity {
+        self.as_ref().default_severity()
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        self.as_ref().fixable()
+    }
+
+    fn depends_on_vault(&self) -> bool {
+        self.as_ref().depends_on_vault()
+    }
+
+    fn docs(&self) -> Docs {
+        self.as_ref().docs()
+    }
+
+    fn default_options(&self) -> Options {
+        self.as_ref().default_options()
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        self.as_ref().with_options(options)
+    }
+
     fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
         self.as_ref().check(content, note)
     }
@@ -1,24 +1,133 @@
 use crate::{
     Note,
-    rule::{Category, Content, Rule, Violation},
+    rule::{
+        Applicability, Category, Content, Docs, DynRule, Options, PathFilter, Rule, Severity,
+        Violation, options::Error as OptionsError,
+    },
+    tags,
 };
-use std::ops::Deref;
+use std::{collections::BTreeSet, ops::Deref, path::Path};
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct ToggleableRule<R>
 where
     R: Rule,
 {
     rule: R,
     enabled: bool,
+
+    /// Severity from config. If `None`, use [`Rule::default_severity`]
+    severity: Option<Severity>,
+
+    /// Notes which are checked by rule
+    paths: PathFilter,
+
+    /// Notes with these tags are not checked by rule
+    ignore_tags: Vec<String>,
+
+    /// Options from config
+    options: Options,
+
+    /// Rule configured by [`options`](Self::options). `None` if options don't change rule
+    configured: Option<DynRule<R::Error>>,
+}
+
+// Manual impl, because derive requires `R::Error: Clone`
+impl<R> Clone for ToggleableRule<R>
+where
+    R: Rule + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            rule: self.rule.clone(),
+            enabled: self.enabled,
+            severity: self.severity,
+            paths: self.paths.clone(),
+            ignore_tags: self.ignore_tags.clone(),
+            options: self.options.clone(),
+            configured: self.configured.clone(),
+        }
+    }
 }
 
 impl<R> ToggleableRule<R>
 where
     R: Rule,
 {
-    pub const fn new(rule: R, enabled: bool) -> Self {
-        Self { rule, enabled }
+    pub fn new(rule: R, enabled: bool) -> Self {
+        Self {
+            rule,
+            enabled,
+            severity: None,
+            paths: PathFilter::default(),
+            ignore_tags: Vec::new(),
+            options: Options::new(),
+            configured: None,
+        }
+    }
+
+    /// Override severity of rule
+    #[must_use]
+    pub const fn with_severity(mut self, severity: Option<Severity>) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Check only notes which match globs
+    #[must_use]
+    pub fn with_paths(mut self, paths: PathFilter) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    #[must_use]
+    pub const fn paths(&self) -> &PathFilter {
+        &self.paths
+    }
+
+    /// Rule must check note with `path` relative to the vault
+    #[must_use]
+    pub fn applies_to(&self, path: &Path) -> bool {
+        self.paths.matches(path)
+    }
+
+    /// Skip notes with any of tags
+    #[must_use]
+    pub fn with_ignore_tags(mut self, ignore_tags: Vec<String>) -> Self {
+        self.ignore_tags = ignore_tags;
+        self
+    }
+
+    #[must_use]
+    pub fn ignore_tags(&self) -> &[String] {
+        &self.ignore_tags
+    }
+
+    /// Rule must skip note with `tags`. See [`tags::find`]
+    #[must_use]
+    pub fn ignores_tags(&self, tags: &BTreeSet<String>) -> bool {
+        tags::contains_any(tags, &self.ignore_tags)
+    }
+
+    /// Configure rule by options from config
+    pub fn configure(mut self, options: Options) -> Result<Self, OptionsError> {
+        self.configured = self.rule.with_options(&options)?;
+        self.options = options;
+        Ok(self)
+    }
+
+    /// Options from config
+    #[must_use]
+    pub const fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// [`Rule::default_options`] with options from config
+    #[must_use]
+    pub fn merged_options(&self) -> Options {
+        let mut options = self.rule.default_options();
+        options.extend(self.options.clone());
+        options
     }
 
     #[must_use]
@@ -38,6 +147,19 @@ where
     pub const fn disable(&mut self) {
         self.enabled = false;
     }
+
+    /// Severity which is set by config
+    #[must_use]
+    pub const fn severity_override(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// Severity of violations found by this rule
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.severity
+            .unwrap_or_else(|| self.rule.default_severity())
+    }
 }
 
 impl<R> Deref for ToggleableRule<R>
@@ -73,10 +195,46 @@ where
         self.deref().category()
     }
 
-    /// If lint is enabled, then run check
+    #[inline]
+    fn default_severity(&self) -> Severity {
+        self.deref().default_severity()
+    }
+
+    #[inline]
+    fn fixable(&self) -> Option<Applicability> {
+        self.deref().fixable()
+    }
+
+    #[inline]
+    fn depends_on_vault(&self) -> bool {
+        self.deref().depends_on_vault()
+    }
+
+    #[inline]
+    fn docs(&self) -> Docs {
+        self.deref().docs()
+    }
+
+    #[inline]
+    fn default_options(&self) -> Options {
+        self.deref().default_options()
+    }
+
+    #[inline]
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        self.deref().with_options(options)
+    }
+
+    /// If lint is enabled, then run check by rule configured with options
     fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
         if self.is_enabled() {
-            return self.deref().check(content, note);
+            return match &self.configured {
+                Some(rule) => rule.check(content, note),
+                None => self.deref().check(content, note),
+            };
         }
 
         Ok(Vec::new())
@@ -88,7 +246,21 @@ where
     R: Rule + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        (&self.rule, self.enabled) == (&other.rule, other.enabled)
+        (
+            &self.rule,
+            self.enabled,
+            self.severity,
+            &self.paths,
+            &self.ignore_tags,
+            &self.options,
+        ) == (
+            &other.rule,
+            other.enabled,
+            other.severity,
+            &other.paths,
+            &other.ignore_tags,
+            &other.options,
+        )
     }
 }
 
@@ -97,9 +269,12 @@ impl<L> Eq for ToggleableRule<L> where L: Rule + PartialEq {}
 #[cfg(test)]
 mod tests {
     use crate::Note;
-    use crate::rule::{Category, Content, Rule, ToggleableRule, Violation};
-    use crate::test_utils::TestRule;
-    use std::sync::Arc;
+    use crate::rule::{
+        Category, Content, Options, PathFilter, Rule, Severity, ToggleableRule, Violation,
+        options::Error as OptionsError,
+    };
+    use crate::test_utils::{MessageRule, TestRule};
+    use std::{collections::BTreeSet, path::Path, sync::Arc};
     use tracing_test::traced_test;
 
     #[test]
@@ -164,4 +339,77 @@ mod tests {
 
         assert!(result.is_empty());
     }
+
+    #[test]
+    #[traced_test]
+    fn severity() {
+        let rule = TestRule::new("test-rule", "", Category::Other, []);
+        let rule = ToggleableRule::new(rule, true);
+
+        assert_eq!(rule.severity(), Severity::Warning);
+        assert_eq!(rule.severity_override(), None);
+
+        let rule = rule.with_severity(Some(Severity::Error));
+
+        assert_eq!(rule.severity(), Severity::Error);
+        assert_eq!(rule.severity_override(), Some(Severity::Error));
+    }
+
+    #[test]
+    #[traced_test]
+    fn paths() {
+        let rule = TestRule::new("test-rule", "", Category::Other, []);
+        let rule = ToggleableRule::new(rule, true);
+
+        assert!(rule.applies_to(Path::new("daily/note.md")));
+
+        let rule = rule.with_paths(PathFilter::new(["projects/**"], ["daily/**"]).unwrap());
+
+        assert!(rule.applies_to(Path::new("projects/note.md")));
+        assert!(!rule.applies_to(Path::new("daily/note.md")));
+        assert!(!rule.applies_to(Path::new("note.md")));
+    }
+
+    #[test]
+    #[traced_test]
+    fn ignore_tags() {
+        let rule = TestRule::new("test-rule", "", Category::Other, []);
+        let rule = ToggleableRule::new(rule, true).with_ignore_tags(vec!["#draft".to_string()]);
+
+        let tags = BTreeSet::from(["draft/wip".to_string()]);
+        assert!(rule.ignores_tags(&tags));
+        assert!(!rule.ignores_tags(&BTreeSet::new()));
+    }
+
+    #[test]
+    #[traced_test]
+    fn options() {
+        let rule = ToggleableRule::new(MessageRule::default(), true);
+        let note = Note::default();
+        let content = Content::default();
+
+        assert_eq!(
+            rule.check(&content, &note).unwrap()[0].message(),
+            "Default message"
+        );
+
+        let options: Options = toml::from_str("violation-message = \"Custom\"").unwrap();
+        let rule = rule.configure(options.clone()).unwrap();
+
+        assert_eq!(rule.check(&content, &note).unwrap()[0].message(), "Custom");
+        assert_eq!(rule.options(), &options);
+        assert_eq!(rule.merged_options(), options);
+
+        let unknown: Options = toml::from_str("unknown = 1").unwrap();
+        assert!(
+            ToggleableRule::new(MessageRule::default(), true)
+                .configure(unknown.clone())
+                .is_err()
+        );
+        assert!(matches!(
+            ToggleableRule::new(TestRule::new("test-rule", "", Category::Other, []), true)
+                .configure(unknown),
+            Err(OptionsError::Unknown(keys)) if keys == ["unknown"]
+        ));
+    }
 }
@@ -1,11 +1,14 @@
+use super::Fix;
+use serde::{Deserialize, Serialize};
 use std::ops::{Bound, Range, RangeBounds};
 use thiserror::Error;
 use tracing::instrument;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Violation {
     message: String,
     location: Range<usize>,
+    fix: Option<Fix>,
 }
 
 #[derive(Debug, Error, PartialEq, Eq, Clone, Copy)]
@@ -45,25 +48,41 @@ impl Violation {
         Ok(Self {
             message: message.into(),
             location: start..end,
+            fix: None,
         })
     }
 
+    /// Attach fix to violation
+    #[must_use]
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
     #[inline]
     #[must_use]
     pub fn message(&self) -> &str {
         &self.message
     }
 
+    /// Byte range in the source of note
     #[inline]
     #[must_use]
     pub const fn location(&self) -> &Range<usize> {
         &self.location
     }
+
+    #[inline]
+    #[must_use]
+    pub const fn fix(&self) -> Option<&Fix> {
+        self.fix.as_ref()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rule::Edit;
     use tracing_test::traced_test;
 
     #[test]
@@ -75,7 +94,8 @@ mod tests {
             violation,
             Violation {
                 message: "Super error".to_string(),
-                location: 43..50
+                location: 43..50,
+                fix: None,
             }
         );
     }
@@ -90,7 +110,8 @@ mod tests {
             violation,
             Violation {
                 message: "Super error".to_string(),
-                location: 43..51
+                location: 43..51,
+                fix: None,
             }
         );
 
@@ -98,7 +119,8 @@ mod tests {
             violation1,
             Violation {
                 message: "Super error".to_string(),
-                location: 43..44
+                location: 43..44,
+                fix: None,
             }
         );
     }
@@ -124,4 +146,15 @@ mod tests {
         let result = Violation::new("Super error", 50..20);
         assert_eq!(result, Err(Error::InvalidRange { start: 50, end: 20 }));
     }
+
+    #[test]
+    #[traced_test]
+    fn with_fix() {
+        let fix = Fix::safe([Edit::delete(43..50)]);
+        let violation = Violation::new("Super error", 43..50)
+            .unwrap()
+            .with_fix(fix.clone());
+
+        assert_eq!(violation.fix(), Some(&fix));
+    }
 }
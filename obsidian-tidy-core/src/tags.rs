@@ -0,0 +1,138 @@
+//! Tags of note from frontmatter and text
+//!
+//! Tags are compared without `#` and case, like in Obsidian.
+//! Nested tag `#draft/wip` matches `#draft` too
+
+use crate::code_blocks;
+use crate::frontmatter::Frontmatter;
+use serde::Deserialize;
+use serde_yaml_ng::Value;
+use std::collections::BTreeSet;
+use tracing::debug;
+
+/// Tag without `#` in lower case
+#[must_use]
+pub fn normalize(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Normalized `tag` is `pattern` or nested tag of `pattern`
+#[must_use]
+pub fn matches(tag: &str, pattern: &str) -> bool {
+    let pattern = normalize(pattern);
+
+    tag.strip_prefix(&pattern)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Any tag of note matches any of `patterns`
+#[must_use]
+pub fn contains_any(tags: &BTreeSet<String>, patterns: &[String]) -> bool {
+    tags.iter()
+        .any(|tag| patterns.iter().any(|pattern| matches(tag, pattern)))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Properties {
+    #[serde(default)]
+    tags: Option<Value>,
+
+    #[serde(default)]
+    tag: Option<Value>,
+}
+
+fn from_value(value: &Value, tags: &mut BTreeSet<String>) {
+    match value {
+        Value::String(text) => tags.extend(
+            text.split([',', ' '])
+                .filter(|tag| !tag.trim().is_empty())
+                .map(normalize),
+        ),
+        Value::Sequence(values) => {
+            for value in values {
+                from_value(value, tags);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_tag_char(character: char) -> bool {
+    character.is_alphanumeric() || matches!(character, '_' | '-' | '/')
+}
+
+/// Inline tags like `#tag` outside of code blocks
+fn from_text(text: &str, tags: &mut BTreeSet<String>) {
+    for (_, line) in code_blocks::lines_outside(text) {
+        for (index, _) in line.match_indices('#') {
+            let preceded = line[..index]
+                .chars()
+                .next_back()
+                .is_none_or(char::is_whitespace);
+
+            let tag: String = line[index + 1..]
+                .chars()
+                .take_while(|character| is_tag_char(*character))
+                .collect();
+
+            // `#123` isn't a tag in Obsidian
+            if preceded
+                && tag
+                    .chars()
+                    .any(|character| !character.is_numeric() && character != '/')
+            {
+                tags.insert(normalize(&tag));
+            }
+        }
+    }
+}
+
+/// Find tags of note in frontmatter `tags` and `tag` and in text
+#[must_use]
+pub fn find(source: &str) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    let mut text = source;
+
+    if let Some(frontmatter) = Frontmatter::find(source) {
+        text = &source[frontmatter.location().end..];
+
+        match frontmatter.parse::<Properties>() {
+            Ok(properties) => {
+                for value in [properties.tags, properties.tag].iter().flatten() {
+                    from_value(value, &mut tags);
+                }
+            }
+            Err(error) => debug!("Failed parse frontmatter: {error}"),
+        }
+    }
+
+    from_text(text, &mut tags);
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_tags() {
+        let source = "---\ntags: [Draft, '#project/a']\ntag: idea, todo\n---\n\
+                      # Heading\n\
+                      Text #inline and #nested/tag, not#tag or #123\n\
+                      ```\n#code\n```\n~~~\n#tilde\n~~~\n";
+
+        let tags: Vec<_> = find(source).into_iter().collect();
+
+        assert_eq!(
+            tags,
+            ["draft", "idea", "inline", "nested/tag", "project/a", "todo"]
+        );
+    }
+
+    #[test]
+    fn match_nested() {
+        assert!(matches("draft", "#Draft"));
+        assert!(matches("draft/wip", "draft"));
+        assert!(!matches("drafts", "draft"));
+    }
+}
@@ -2,9 +2,13 @@
 
 use crate::{
     Note,
-    rule::{Category, Content, Rule, Violation},
+    rule::{
+        Category, Content, DynRule, Options, Rule, Violation,
+        options::{Error as OptionsError, from_options, to_options},
+    },
 };
-use std::convert::Infallible;
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, sync::Arc};
 
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) struct TestRule {
@@ -50,6 +54,57 @@ impl Rule for TestRule {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct MessageOptions {
+    pub violation_message: String,
+}
+
+impl Default for MessageOptions {
+    fn default() -> Self {
+        Self {
+            violation_message: "Default message".to_string(),
+        }
+    }
+}
+
+/// Rule with options which returns one violation with message from options
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct MessageRule(pub MessageOptions);
+
+impl Rule for MessageRule {
+    type Error = Infallible;
+
+    fn name(&self) -> &'static str {
+        "message-rule"
+    }
+
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    fn category(&self) -> Category {
+        Category::Other
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&MessageOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self(from_options(options)?))))
+    }
+
+    fn check(&self, _content: &Content, _note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        Ok(vec![
+            Violation::new(&self.0.violation_message, 0..0).unwrap(),
+        ])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
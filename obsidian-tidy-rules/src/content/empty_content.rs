@@ -1,14 +1,39 @@
 //! Rule for search notes with empty content
 
 use obsidian_parser::note::Note as _;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
 use obsidian_tidy_core::rule::violation::{Error as ViolationError, Violation};
-use obsidian_tidy_core::rule::{Category, Content, Rule};
+use obsidian_tidy_core::rule::{Category, Content, Docs, DynRule, Options, Rule};
 use obsidian_tidy_core::{Note, NoteError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use thiserror::Error;
 use tracing::{instrument, trace};
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct EmptyContentOptions {
+    /// Note with fewer words is empty
+    pub min_words: usize,
+}
+
+impl Default for EmptyContentOptions {
+    fn default() -> Self {
+        Self { min_words: 1 }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct EmptyContent;
+pub struct EmptyContent {
+    options: EmptyContentOptions,
+}
+
+impl EmptyContent {
+    #[must_use]
+    pub const fn new(options: EmptyContentOptions) -> Self {
+        Self { options }
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -34,12 +59,39 @@ impl Rule for EmptyContent {
         Category::Content
     }
 
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Empty notes are usually leftovers of unfinished ideas or links to missing notes. \
+             They clutter search, quick switcher and graph without adding any knowledge.",
+        )
+        .bad("---\ntags: [idea]\n---\n")
+        .good("---\ntags: [idea]\n---\nLink notes by meaning, not by folders.\n")
+        .option("min-words", "Note with fewer words is empty")
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&EmptyContentOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(from_options(options)?))))
+    }
+
     #[instrument(skip(_content))]
     fn check(&self, _content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
         trace!("Run check `EmptyContent`");
 
-        if note.count_words_from_content()? == 0 {
-            let violation = Violation::new("Note is empty", 1..=1)?;
+        if note.count_words_from_content()? < self.options.min_words {
+            let message = if self.options.min_words > 1 {
+                format!("Note has fewer than {} words", self.options.min_words)
+            } else {
+                "Note is empty".to_string()
+            };
+
+            let violation = Violation::new(message, 1..=1)?;
             return Ok(vec![violation]);
         }
 
@@ -78,7 +130,7 @@ mod tests {
     #[test]
     #[traced_test]
     fn empty_note() {
-        let rule = EmptyContent;
+        let rule = EmptyContent::default();
 
         let note = Note::from_string_default("").unwrap();
         let violation = rule.check(&Content::default(), &note).unwrap();
@@ -89,7 +141,7 @@ mod tests {
     #[test]
     #[traced_test]
     fn not_empty_note() {
-        let rule = EmptyContent;
+        let rule = EmptyContent::default();
 
         let note = Note::from_string_default("Super data").unwrap();
         let violation = rule.check(&Content::default(), &note).unwrap();
@@ -100,7 +152,7 @@ mod tests {
     #[test]
     #[traced_test]
     fn generated_note() {
-        let rule = EmptyContent;
+        let rule = EmptyContent::default();
 
         let mut generator = DefaultNoteGenerator::default();
         let mut note = generator.generate_temp_note().unwrap();
@@ -113,7 +165,7 @@ mod tests {
     #[test]
     #[traced_test]
     fn not_empty_notes() {
-        let rule = EmptyContent;
+        let rule = EmptyContent::default();
 
         let violations = DEFAULT_MOCK_VAULT.run_rule(&rule);
         assert!(violations.is_empty());
@@ -122,7 +174,7 @@ mod tests {
     #[test]
     #[traced_test]
     fn with_empty_notes() {
-        let rule = EmptyContent;
+        let rule = EmptyContent::default();
 
         let mock_vault = MockVaultBuilder::<MyGenerator>::default()
             .count_notes(10)
@@ -135,7 +187,7 @@ mod tests {
 
     #[test]
     fn empty_vault() {
-        let rule = EmptyContent;
+        let rule = EmptyContent::default();
 
         let mock_vault = MockVaultBuilder::<DefaultNoteGenerator>::default()
             .count_notes(0)
@@ -145,4 +197,20 @@ mod tests {
         let violations = mock_vault.run_rule(&rule);
         assert!(violations.is_empty());
     }
+
+    #[test]
+    #[traced_test]
+    fn min_words() {
+        let options = to_options(&EmptyContentOptions { min_words: 3 });
+        let rule = EmptyContent::default()
+            .with_options(&options)
+            .unwrap()
+            .unwrap();
+
+        let note = Note::from_string_default("Two words").unwrap();
+        let violations = rule.check(&Content::default(), &note).unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message(), "Note has fewer than 3 words");
+    }
 }
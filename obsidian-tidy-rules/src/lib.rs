@@ -3,13 +3,64 @@
 #![forbid(clippy::print_stdout)]
 
 pub mod content;
+pub mod lua;
+pub mod pattern;
 pub mod rules;
+pub mod wasm;
+pub mod yaml;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
 
-use obsidian_tidy_core::rule::SharedErrorRule;
+use obsidian_tidy_core::rule::{Rule, SharedErrorRule};
 use rules::rules;
-use std::sync::LazyLock;
+use std::{path::Path, sync::LazyLock};
+use thiserror::Error;
 
-pub static ALL_RULES: LazyLock<Vec<SharedErrorRule>> = rules![content::empty_content::EmptyContent];
+pub static ALL_RULES: LazyLock<Vec<SharedErrorRule>> = rules![
+    content::empty_content::EmptyContent::default(),
+    yaml::missing_frontmatter::MissingFrontmatter,
+    yaml::invalid_frontmatter::InvalidFrontmatter,
+    yaml::required_keys::RequiredKeys::default(),
+    yaml::forbidden_keys::ForbiddenKeys::default(),
+    yaml::key_order::KeyOrder::default(),
+    yaml::key_case::KeyCase::default(),
+    yaml::duplicate_keys::DuplicateKeys,
+    yaml::list_format::ListFormat::default(),
+    yaml::frontmatter_schema::FrontmatterSchema::default(),
+];
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Lua rule: {0}")]
+    Lua(#[from] lua::Error),
+
+    #[error("WASM rule: {0}")]
+    Wasm(#[from] wasm::Error),
+
+    #[error("Rule `{0}` already exists")]
+    Duplicate(String),
+}
+
+/// Folder of vault with custom rules
+pub const CUSTOM_RULES_DIR: &str = ".obsidian-tidy/rules";
+
+/// [`ALL_RULES`] with custom rules of vault in `root`: Lua scripts and WASM plugins
+pub fn available_rules(root: &Path) -> Result<Vec<SharedErrorRule>, Error> {
+    let dir = root.join(CUSTOM_RULES_DIR);
+    let mut rules = ALL_RULES.clone();
+
+    let custom = lua::load_dir(&dir)?
+        .into_iter()
+        .chain(wasm::load_dir(&dir)?);
+
+    for rule in custom {
+        if rules.iter().any(|known| known.name() == rule.name()) {
+            return Err(Error::Duplicate(rule.name().to_string()));
+        }
+
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
@@ -0,0 +1,108 @@
+//! Custom rules in Lua scripts
+//!
+//! Script defines function `check(note, vault, options)` which returns `nil`,
+//! one violation `{ message = "...", from = 1, to = 5 }` or array of them.
+//! `from` and `to` are 1-based inclusive byte positions in `note.text`, like results of `string.find`.
+//! Violation without position points to the start of note.
+//!
+//! `note` has fields `path`, `name`, `text`, `body` (text without frontmatter), `frontmatter`,
+//! `tags` and `links` (`target`, `note`, `alias`, `embed`, `from`, `to`).
+//! `vault` is `nil` unless script sets global `depends_on_vault = true`.
+//! Then it has `notes` (`path`, `name`, `links`, `unresolved`), `backlinks` by path
+//! and function `resolve(target)` which returns path of linked note.
+//!
+//! Optional function `fix(note, options)` returns changed `note` or new text of note.
+//! Its fix is attached to the first violation.
+//!
+//! Optional globals: `name` (file name by default), `description`, `category`, `severity`,
+//! `options` with default values, `rationale`, `bad` and `good` for docs.
+//! Helpers are in table `lib`
+//!
+//! Scripts of vault run on opening it, so they are sandboxed. Only libraries `string`, `table`,
+//! `math` and `utf8` are loaded. Every call runs at most [`MAX_INSTRUCTIONS`] instructions
+//! and script uses at most [`MAX_MEMORY`] bytes
+
+mod rule;
+mod vault;
+
+pub use rule::LuaRule;
+
+use obsidian_tidy_core::rule::SharedErrorRule;
+use obsidian_tidy_core::rule::content::Error as ContentError;
+use obsidian_tidy_core::rule::severity::ParseSeverityError;
+use obsidian_tidy_core::rule::violation::Error as ViolationError;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+/// Extension of scripts
+pub const EXTENSION: &str = "lua";
+
+/// Instructions of one call of script. Script which runs out of them fails
+pub const MAX_INSTRUCTIONS: u64 = 100_000_000;
+
+/// Memory of script in bytes
+pub const MAX_MEMORY: usize = 64 << 20;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("Lua error: {0}")]
+    Lua(#[from] mlua::Error),
+
+    #[error("Failed read note: {0}")]
+    Content(#[from] ContentError),
+
+    #[error("Failed create violation: {0}")]
+    Violation(#[from] ViolationError),
+
+    #[error("Failed write frontmatter: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+
+    #[error("{0}")]
+    Severity(#[from] ParseSeverityError),
+
+    #[error("Script `{}` has no function `check`", .0.display())]
+    NoCheck(PathBuf),
+
+    #[error("Unknown category `{0}`")]
+    UnknownCategory(String),
+}
+
+/// Scripts in `dir` sorted by path. Missing `dir` has no scripts
+pub fn scripts(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut scripts = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION)
+        {
+            scripts.push(path);
+        }
+    }
+
+    scripts.sort();
+    Ok(scripts)
+}
+
+/// Load rules from scripts in `dir`
+#[instrument(err)]
+pub fn load_dir(dir: &Path) -> Result<Vec<SharedErrorRule>, Error> {
+    let scripts = scripts(dir)?;
+    debug!("Found {} Lua rules", scripts.len());
+
+    scripts
+        .iter()
+        .map(|path| Ok(SharedErrorRule::new(LuaRule::new(path)?)))
+        .collect()
+}
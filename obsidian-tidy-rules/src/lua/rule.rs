@@ -0,0 +1,685 @@
+//! Rule from Lua script
+
+use super::{Error, MAX_INSTRUCTIONS, MAX_MEMORY, vault};
+use mlua::{
+    FromLuaMulti, Function, HookTriggers, IntoLuaMulti, Lua, LuaOptions, LuaSerdeExt, RegistryKey,
+    SerializeOptions, StdLib, Table, Value, VmState,
+};
+use obsidian_parser::note::Note as _;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, merge};
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Content, Docs, DynRule, Edit, Fix, Options, Rule, Severity, Violation,
+};
+use obsidian_tidy_core::{Note, Vault, links, tags};
+use serde::Deserialize;
+use serde_yaml_ng::{Mapping, Value as YamlValue};
+use std::{
+    path::Path,
+    sync::{
+        Arc, Mutex, PoisonError, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{debug, instrument, trace};
+
+/// Helpers for scripts
+const PRELUDE: &str = r"
+lib = {}
+
+function lib.tbl_contains(tbl, value)
+    for _, item in ipairs(tbl) do
+        if item == value then
+            return true
+        end
+    end
+
+    return false
+end
+";
+
+/// Instructions between checks of limit
+const HOOK_INSTRUCTIONS: u32 = 10_000;
+
+/// Lua without access to files, processes and modules.
+/// `instructions` counts instructions of the current call
+fn sandbox(instructions: Arc<AtomicU64>) -> mlua::Result<Lua> {
+    let lua = Lua::new_with(
+        StdLib::STRING | StdLib::TABLE | StdLib::MATH | StdLib::UTF8,
+        LuaOptions::default(),
+    )?;
+
+    // Base library can read and run files
+    let globals = lua.globals();
+    globals.raw_set("dofile", Value::Nil)?;
+    globals.raw_set("loadfile", Value::Nil)?;
+
+    lua.set_memory_limit(MAX_MEMORY)?;
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(HOOK_INSTRUCTIONS),
+        move |_, _| {
+            let count = instructions.fetch_add(u64::from(HOOK_INSTRUCTIONS), Ordering::Relaxed);
+
+            if count >= MAX_INSTRUCTIONS {
+                return Err(mlua::Error::runtime(format!(
+                    "Script exceeded limit of {MAX_INSTRUCTIONS} instructions"
+                )));
+            }
+
+            Ok(VmState::Continue)
+        },
+    );
+
+    Ok(lua)
+}
+
+/// `nil` in Lua for missing values
+const fn serialize_options() -> SerializeOptions {
+    SerializeOptions::new()
+        .serialize_none_to_null(false)
+        .serialize_unit_to_null(false)
+}
+
+#[derive(Debug, Deserialize)]
+struct LuaViolation {
+    message: String,
+    from: Option<usize>,
+    to: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CheckResult {
+    One(LuaViolation),
+    Many(Vec<LuaViolation>),
+}
+
+/// Note returned by `fix`
+#[derive(Debug, Deserialize)]
+struct FixedNote {
+    text: Option<String>,
+    body: Option<String>,
+    frontmatter: Option<YamlValue>,
+}
+
+/// `new` frontmatter with keys in order of `original`. New keys are at the end
+fn ordered(original: Option<&YamlValue>, new: YamlValue) -> YamlValue {
+    let YamlValue::Mapping(mut new) = new else {
+        return new;
+    };
+
+    let Some(YamlValue::Mapping(original)) = original else {
+        return YamlValue::Mapping(new);
+    };
+
+    let mut mapping: Mapping = original
+        .keys()
+        .filter_map(|key| new.remove_entry(key))
+        .collect();
+    mapping.extend(new);
+
+    YamlValue::Mapping(mapping)
+}
+
+/// Text of note after `fix`
+fn fixed_text(source: &str, fixed: FixedNote) -> Result<String, Error> {
+    if let Some(text) = fixed.text.filter(|text| text != source) {
+        return Ok(text);
+    }
+
+    let frontmatter = Frontmatter::find(source);
+    let body_start = frontmatter
+        .as_ref()
+        .map_or(0, |frontmatter| frontmatter.location().end);
+    let original = frontmatter
+        .and_then(|frontmatter| frontmatter.parse::<YamlValue>().ok())
+        .filter(|frontmatter| !frontmatter.is_null());
+
+    let body = fixed
+        .body
+        .unwrap_or_else(|| source[body_start..].to_string());
+
+    if fixed.frontmatter == original {
+        return Ok(format!("{}{body}", &source[..body_start]));
+    }
+
+    let Some(frontmatter) = fixed.frontmatter else {
+        return Ok(body);
+    };
+
+    let yaml = serde_yaml_ng::to_string(&ordered(original.as_ref(), frontmatter))?;
+    Ok(format!("---\n{yaml}---\n{body}"))
+}
+
+/// The smallest edit which turns `old` into `new`
+fn edit(old: &str, new: &str) -> Option<Edit> {
+    if old == new {
+        return None;
+    }
+
+    let mut prefix = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    while !old.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let mut suffix = old
+        .bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(old.len().min(new.len()) - prefix)
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    while !old.is_char_boundary(old.len() - suffix) {
+        suffix -= 1;
+    }
+
+    Some(Edit::replace(
+        prefix..old.len() - suffix,
+        &new[prefix..new.len() - suffix],
+    ))
+}
+
+/// Loaded script shared by rules with different options
+struct Script {
+    lua: Lua,
+    name: String,
+    description: String,
+    category: Category,
+    severity: Severity,
+    depends_on_vault: bool,
+    fixable: bool,
+    options: Options,
+    docs: Docs,
+
+    /// Table of the last checked vault
+    vault: Mutex<Option<(Weak<Vault>, RegistryKey)>>,
+
+    /// Instructions of the current call
+    instructions: Arc<AtomicU64>,
+}
+
+impl Script {
+    fn new(source: &str, path: &Path) -> Result<Self, Error> {
+        let instructions = Arc::new(AtomicU64::new(0));
+        let lua = sandbox(instructions.clone())?;
+        lua.load(PRELUDE).set_name("prelude").exec()?;
+        lua.load(source).set_name(path.to_string_lossy()).exec()?;
+
+        let globals = lua.globals();
+
+        if globals.get::<Option<Function>>("check")?.is_none() {
+            return Err(Error::NoCheck(path.to_path_buf()));
+        }
+
+        let file_name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let name = globals.get::<Option<String>>("name")?.unwrap_or(file_name);
+        let description = globals
+            .get::<Option<String>>("description")?
+            .unwrap_or_else(|| format!("Custom rule from `{}`", path.display()));
+
+        let category = match globals.get::<Option<String>>("category")? {
+            Some(category) => Category::ALL
+                .into_iter()
+                .find(|known| known.as_str() == category)
+                .ok_or(Error::UnknownCategory(category))?,
+            None => Category::Other,
+        };
+
+        let severity = match globals.get::<Option<String>>("severity")? {
+            Some(severity) => severity.parse()?,
+            None => Severity::default(),
+        };
+
+        let options = match globals.get::<Value>("options")? {
+            Value::Nil => Options::new(),
+            options => lua.from_value(options)?,
+        };
+
+        let mut docs = Docs::new(
+            globals
+                .get::<Option<String>>("rationale")?
+                .unwrap_or_default(),
+        );
+
+        if let Some(bad) = globals.get::<Option<String>>("bad")? {
+            docs = docs.bad(bad);
+        }
+
+        if let Some(good) = globals.get::<Option<String>>("good")? {
+            docs = docs.good(good);
+        }
+
+        Ok(Self {
+            name,
+            description,
+            category,
+            severity,
+            depends_on_vault: globals
+                .get::<Option<bool>>("depends_on_vault")?
+                .unwrap_or_default(),
+            fixable: globals.get::<Option<Function>>("fix")?.is_some(),
+            options,
+            docs,
+            vault: Mutex::new(None),
+            instructions,
+            lua,
+        })
+    }
+
+    /// Call `function` of script with new limit of instructions
+    fn call<R: FromLuaMulti>(
+        &self,
+        function: &Function,
+        args: impl IntoLuaMulti,
+    ) -> mlua::Result<R> {
+        self.instructions.store(0, Ordering::Relaxed);
+        function.call(args)
+    }
+
+    /// Table of note for script
+    fn note(&self, source: &str, path: Option<&Path>) -> mlua::Result<Table> {
+        let lua = &self.lua;
+        let note = lua.create_table()?;
+
+        if let Some(path) = path {
+            note.set("path", path.to_string_lossy().into_owned())?;
+            note.set(
+                "name",
+                path.file_stem()
+                    .map(|name| name.to_string_lossy().into_owned()),
+            )?;
+        }
+
+        let frontmatter = Frontmatter::find(source);
+        let body = frontmatter
+            .as_ref()
+            .map_or(source, |frontmatter| &source[frontmatter.location().end..]);
+
+        note.set("text", source)?;
+        note.set("body", body)?;
+
+        if let Some(frontmatter) =
+            frontmatter.and_then(|frontmatter| frontmatter.parse::<YamlValue>().ok())
+        {
+            note.set(
+                "frontmatter",
+                lua.to_value_with(&frontmatter, serialize_options())?,
+            )?;
+        }
+
+        note.set("tags", lua.create_sequence_from(tags::find(source))?)?;
+
+        let links = lua.create_table()?;
+        for link in links::find(source) {
+            let table = lua.create_table()?;
+            table.set("target", link.target)?;
+            table.set("note", link.note())?;
+            table.set("alias", link.alias)?;
+            table.set("embed", link.embed)?;
+            table.set("from", link.location.start + 1)?;
+            table.set("to", link.location.end)?;
+
+            links.push(table)?;
+        }
+
+        note.set("links", links)?;
+        Ok(note)
+    }
+
+    /// Table of vault. It is built once for each vault
+    fn vault(&self, content: &Content) -> mlua::Result<Table> {
+        let mut cache = self.vault.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let cached = cache.as_ref().filter(|(vault, _)| {
+            vault
+                .upgrade()
+                .is_some_and(|vault| Arc::ptr_eq(&vault, &content.vault))
+        });
+
+        if let Some((_, key)) = cached {
+            return self.lua.registry_value(key);
+        }
+
+        debug!("Build vault for Lua rule `{}`", self.name);
+
+        let table = vault::table(&self.lua, content)?;
+        let key = self.lua.create_registry_value(table.clone())?;
+
+        *cache = Some((Arc::downgrade(&content.vault), key));
+        self.lua.expire_registry_values();
+
+        Ok(table)
+    }
+
+    fn violations(&self, result: Value) -> Result<Vec<Violation>, Error> {
+        let result: CheckResult = match result {
+            Value::Nil | Value::Boolean(false) => return Ok(Vec::new()),
+            Value::Table(table) if table.is_empty() => return Ok(Vec::new()),
+            result => self.lua.from_value(result)?,
+        };
+
+        let violations = match result {
+            CheckResult::One(violation) => vec![violation],
+            CheckResult::Many(violations) => violations,
+        };
+
+        violations
+            .into_iter()
+            .map(|violation| {
+                let location = match (violation.from, violation.to) {
+                    (None, None) => 0..0,
+                    (from, to) => {
+                        let to = to.or(from).unwrap_or_default();
+                        from.unwrap_or(to).saturating_sub(1)..to
+                    }
+                };
+
+                Ok(Violation::new(violation.message, location)?)
+            })
+            .collect()
+    }
+
+    fn fix(&self, source: &str, path: Option<&Path>, options: Value) -> Result<Option<Fix>, Error> {
+        let Some(fix) = self.lua.globals().get::<Option<Function>>("fix")? else {
+            return Ok(None);
+        };
+
+        let fixed = match self.call::<Value>(&fix, (self.note(source, path)?, options))? {
+            Value::Nil | Value::Boolean(false) => return Ok(None),
+            Value::String(text) => text.to_string_lossy(),
+            fixed => fixed_text(source, self.lua.from_value(fixed)?)?,
+        };
+
+        Ok(edit(source, &fixed).map(|edit| Fix::safe([edit])))
+    }
+}
+
+/// Rule from Lua script. See [module](super) for API of scripts
+#[derive(Clone)]
+pub struct LuaRule {
+    script: Arc<Script>,
+    options: Options,
+}
+
+impl LuaRule {
+    /// Load rule from script in `path`
+    #[instrument(err)]
+    pub fn new(path: &Path) -> Result<Self, Error> {
+        let source = std::fs::read_to_string(path)?;
+        Self::from_source(&source, path)
+    }
+
+    /// Load rule from `source` of script. `path` names rule and errors
+    pub fn from_source(source: &str, path: &Path) -> Result<Self, Error> {
+        let script = Script::new(source, path)?;
+
+        Ok(Self {
+            options: script.options.clone(),
+            script: Arc::new(script),
+        })
+    }
+}
+
+impl Rule for LuaRule {
+    type Error = super::Error;
+
+    fn name(&self) -> &str {
+        &self.script.name
+    }
+
+    fn description(&self) -> &str {
+        &self.script.description
+    }
+
+    fn category(&self) -> Category {
+        self.script.category.clone()
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.script.severity
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        self.script.fixable.then_some(Applicability::Safe)
+    }
+
+    fn depends_on_vault(&self) -> bool {
+        self.script.depends_on_vault
+    }
+
+    fn docs(&self) -> Docs {
+        self.script.docs.clone()
+    }
+
+    fn default_options(&self) -> Options {
+        self.script.options.clone()
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self {
+            script: self.script.clone(),
+            options: merge(&self.script.options, options)?,
+        })))
+    }
+
+    #[instrument(skip_all, fields(rule = self.name()))]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check of Lua rule");
+
+        let script = &self.script;
+        let source = content.source(note)?;
+        let path = note.path();
+
+        let vault = if script.depends_on_vault {
+            Value::Table(script.vault(content)?)
+        } else {
+            Value::Nil
+        };
+
+        let options = script.lua.to_value(&self.options)?;
+        let check: Function = script.lua.globals().get("check")?;
+        let result = script.call(
+            &check,
+            (
+                script.note(&source, path.as_deref())?,
+                vault,
+                options.clone(),
+            ),
+        )?;
+
+        let mut violations = script.violations(result)?;
+
+        let fix = if script.fixable && !violations.is_empty() {
+            script.fix(&source, path.as_deref(), options)?
+        } else {
+            None
+        };
+
+        if let (Some(fix), Some(first)) = (fix, violations.first_mut()) {
+            *first = first.clone().with_fix(fix);
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_core::rule::content::parse_note;
+
+    fn check(script: &str, source: &str) -> Vec<Violation> {
+        let rule = LuaRule::from_source(script, Path::new("rules/my-rule.lua")).unwrap();
+        check_rule(&rule, source)
+    }
+
+    fn check_rule<R: Rule>(rule: &R, source: &str) -> Vec<Violation>
+    where
+        R::Error: std::fmt::Debug,
+    {
+        let note = parse_note(source, "note.md").unwrap();
+        let content = Content::default().with_source("note.md", source);
+
+        rule.check(&content, &note).unwrap()
+    }
+
+    #[test]
+    fn metadata() {
+        let rule = LuaRule::from_source(
+            "category = 'content'\nseverity = 'error'\nfunction check(note) end\nfunction fix(note) end",
+            Path::new("rules/my-rule.lua"),
+        )
+        .unwrap();
+
+        assert_eq!(rule.name(), "my-rule");
+        assert_eq!(rule.category(), Category::Content);
+        assert_eq!(rule.default_severity(), Severity::Error);
+        assert_eq!(rule.fixable(), Some(Applicability::Safe));
+        assert!(!rule.depends_on_vault());
+    }
+
+    #[test]
+    fn no_check() {
+        let rule = LuaRule::from_source("name = 'rule'", Path::new("rule.lua"));
+        assert!(matches!(rule, Err(Error::NoCheck(_))));
+    }
+
+    #[test]
+    fn violations() {
+        let script = r#"
+            function check(note)
+                local from, to = string.find(note.text, "TODO")
+                if from then
+                    return { { message = "TODO in note", from = from, to = to } }
+                end
+            end
+        "#;
+
+        let violations = check(script, "Text TODO");
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message(), "TODO in note");
+        assert_eq!(violations[0].location(), &(5..9));
+        assert!(check(script, "Text").is_empty());
+    }
+
+    #[test]
+    fn note_fields() {
+        let script = r##"
+            function check(note)
+                if note.name ~= "note" or note.frontmatter.title ~= "Note"
+                    or not lib.tbl_contains(note.tags, "idea")
+                    or note.links[1].note ~= "Other" or note.body ~= "#idea [[Other]]" then
+                    return { message = "Unexpected note" }
+                end
+            end
+        "##;
+
+        assert!(check(script, "---\ntitle: Note\n---\n#idea [[Other]]").is_empty());
+    }
+
+    #[test]
+    fn fix_frontmatter() {
+        let script = r#"
+            function check(note)
+                if not note.frontmatter.status then
+                    return { message = "Missing status" }
+                end
+            end
+
+            function fix(note)
+                note.frontmatter.status = "draft"
+                return note
+            end
+        "#;
+
+        let source = "---\ntitle: Note\n---\nText\n";
+        let violations = check(script, source);
+        let fix = violations[0].fix().unwrap();
+
+        let (fixed, _) = obsidian_tidy_core::rule::fix::apply_fixes(source, [fix]);
+        assert_eq!(fixed, "---\ntitle: Note\nstatus: draft\n---\nText\n");
+    }
+
+    #[test]
+    fn sandbox() {
+        let script = r#"
+            function check(note)
+                if os ~= nil or io ~= nil or require ~= nil or dofile ~= nil then
+                    return { message = "Unsafe library" }
+                end
+            end
+        "#;
+
+        assert!(check(script, "Text").is_empty());
+
+        let rule = LuaRule::from_source(
+            "function check(note) os.execute('echo unsafe') end",
+            Path::new("rule.lua"),
+        )
+        .unwrap();
+        let note = parse_note("Text", "note.md").unwrap();
+        let content = Content::default().with_source("note.md", "Text");
+
+        assert!(rule.check(&content, &note).is_err());
+    }
+
+    #[test]
+    fn instruction_limit() {
+        let rule = LuaRule::from_source(
+            "function check(note) while true do end end",
+            Path::new("loop.lua"),
+        )
+        .unwrap();
+        let note = parse_note("Text", "note.md").unwrap();
+        let content = Content::default().with_source("note.md", "Text");
+
+        let error = rule.check(&content, &note).unwrap_err();
+        assert!(error.to_string().contains("instructions"), "{error}");
+
+        let loading = LuaRule::from_source("while true do end", Path::new("loop.lua"));
+        assert!(loading.is_err());
+    }
+
+    #[test]
+    fn options() {
+        let script = r#"
+            options = { ["max-length"] = 10 }
+
+            function check(note, vault, options)
+                if #note.text > options["max-length"] then
+                    return { message = "Too long" }
+                end
+            end
+        "#;
+
+        let rule = LuaRule::from_source(script, Path::new("length.lua")).unwrap();
+        assert_eq!(check_rule(&rule, "Long text of note").len(), 1);
+
+        let options: Options = toml::from_str("max-length = 100").unwrap();
+        let configured = rule.with_options(&options).unwrap().unwrap();
+        assert!(check_rule(&configured, "Long text of note").is_empty());
+
+        assert!(
+            rule.with_options(&toml::from_str("other = 1").unwrap())
+                .is_err()
+        );
+        assert!(
+            rule.with_options(&toml::from_str("max-length = 'long'").unwrap())
+                .is_err()
+        );
+    }
+}
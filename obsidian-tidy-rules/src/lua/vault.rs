@@ -0,0 +1,162 @@
+//! Graph of links of vault for Lua rules
+
+use mlua::{Lua, Table};
+use obsidian_parser::prelude::Note as _;
+use obsidian_tidy_core::{links, rule::Content};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
+/// Key of note for resolving links: lower case path or name without `.md`
+fn key(target: &str) -> String {
+    let target = target.replace("%20", " ").replace('\\', "/").to_lowercase();
+    let target = target.trim_start_matches("./");
+
+    target.strip_suffix(".md").unwrap_or(target).to_string()
+}
+
+/// Target of link is file which isn't note, like `image.png`
+fn is_attachment(target: &str) -> bool {
+    Path::new(target)
+        .extension()
+        .is_some_and(|extension| extension != "md")
+}
+
+/// Resolve targets of links to paths of notes like Obsidian
+#[derive(Debug, Clone, Default)]
+struct Resolver {
+    /// Paths by keys of names
+    names: HashMap<String, String>,
+
+    /// Keys of paths with paths
+    paths: Vec<(String, String)>,
+}
+
+impl Resolver {
+    fn insert(&mut self, path: &str, name: &str) {
+        self.names
+            .entry(key(name))
+            .or_insert_with(|| path.to_string());
+
+        self.paths.push((key(path), path.to_string()));
+    }
+
+    /// Path of note for target of link
+    fn resolve(&self, target: &str) -> Option<&str> {
+        let target = key(target);
+
+        if target.is_empty() {
+            return None;
+        }
+
+        if !target.contains('/') {
+            return self.names.get(&target).map(String::as_str);
+        }
+
+        let suffix = format!("/{target}");
+
+        self.paths
+            .iter()
+            .find(|(key, _)| *key == target || key.ends_with(&suffix))
+            .map(|(_, path)| path.as_str())
+    }
+}
+
+/// Table of vault with `notes`, `backlinks` and function `resolve`
+pub(super) fn table(lua: &Lua, content: &Content) -> mlua::Result<Table> {
+    let mut resolver = Resolver::default();
+    let mut sources = Vec::new();
+
+    for note in content.vault.notes() {
+        let Some(path) = note.path() else {
+            continue;
+        };
+
+        let Ok(source) = content.source(note) else {
+            continue;
+        };
+
+        let name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let path = path.to_string_lossy().into_owned();
+
+        resolver.insert(&path, &name);
+        sources.push((path, name, source.into_owned()));
+    }
+
+    let notes = lua.create_table()?;
+    let mut backlinks: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+
+    for (path, name, source) in &sources {
+        let mut linked = Vec::new();
+        let mut unresolved = Vec::new();
+
+        for link in links::find(source) {
+            let target = link.note();
+
+            if target.is_empty() || is_attachment(target) {
+                continue;
+            }
+
+            match resolver.resolve(target) {
+                Some(target) if !linked.contains(&target) => {
+                    linked.push(target);
+                    backlinks.entry(target).or_default().push(path.as_str());
+                }
+                None if !unresolved.contains(&target) => unresolved.push(target),
+                _ => {}
+            }
+        }
+
+        let note = lua.create_table()?;
+        note.set("path", path.as_str())?;
+        note.set("name", name.as_str())?;
+        note.set("links", lua.create_sequence_from(linked)?)?;
+        note.set("unresolved", lua.create_sequence_from(unresolved)?)?;
+
+        notes.push(note)?;
+    }
+
+    let backlinks_table = lua.create_table()?;
+    for (path, sources) in backlinks {
+        backlinks_table.set(path, lua.create_sequence_from(sources)?)?;
+    }
+
+    let resolve = lua.create_function(move |_, target: String| {
+        Ok(resolver.resolve(&target).map(str::to_string))
+    })?;
+
+    let vault = lua.create_table()?;
+    vault.set("notes", notes)?;
+    vault.set("backlinks", backlinks_table)?;
+    vault.set("resolve", resolve)?;
+
+    Ok(vault)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let mut resolver = Resolver::default();
+        resolver.insert("vault/Projects/Plan.md", "Plan");
+        resolver.insert("vault/Archive/Plan.md", "Plan");
+        resolver.insert("vault/Daily note.md", "Daily note");
+
+        assert_eq!(resolver.resolve("plan"), Some("vault/Projects/Plan.md"));
+        assert_eq!(
+            resolver.resolve("Archive/Plan.md"),
+            Some("vault/Archive/Plan.md")
+        );
+        assert_eq!(
+            resolver.resolve("Daily%20note.md"),
+            Some("vault/Daily note.md")
+        );
+        assert_eq!(resolver.resolve("Missing"), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Rule which reports matches of regex. Used by custom rules declared in config
+
+use obsidian_tidy_core::rule::violation::{Error as ViolationError, Violation};
+use obsidian_tidy_core::rule::{Applicability, Category, Content, Edit, Fix, Rule};
+use obsidian_tidy_core::{Note, rule::content::Error as ContentError};
+use regex::Regex;
+use thiserror::Error;
+use tracing::{instrument, trace};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed read note: {0}")]
+    Content(#[from] ContentError),
+
+    #[error("Failed create violation: `{0}`")]
+    Violation(#[from] ViolationError),
+}
+
+/// Every match of `pattern` is violation with `message`.
+/// Matches are replaced with `replacement` by fix, `$1` and `$name` are expanded to groups
+///
+/// # Example
+/// ```
+/// use obsidian_tidy_rules::pattern::PatternRule;
+///
+/// let rule = PatternRule::new("no-http", r"http://(\S+)", "Use HTTPS")
+///     .unwrap()
+///     .with_replacement("https://$1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PatternRule {
+    name: String,
+    description: String,
+    category: Category,
+    regex: Regex,
+    message: String,
+    replacement: Option<String>,
+}
+
+impl PatternRule {
+    pub fn new(
+        name: impl Into<String>,
+        pattern: &str,
+        message: impl Into<String>,
+    ) -> Result<Self, regex::Error> {
+        let message = message.into();
+
+        Ok(Self {
+            name: name.into(),
+            description: message.clone(),
+            category: Category::Other,
+            regex: Regex::new(pattern)?,
+            message,
+            replacement: None,
+        })
+    }
+
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_category(mut self, category: Category) -> Self {
+        self.category = category;
+        self
+    }
+
+    #[must_use]
+    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.replacement = Some(replacement.into());
+        self
+    }
+}
+
+impl Rule for PatternRule {
+    type Error = self::Error;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn category(&self) -> Category {
+        self.category.clone()
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        self.replacement.as_ref().map(|_| Applicability::Safe)
+    }
+
+    #[instrument(skip_all, fields(rule = %self.name))]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check of pattern rule");
+
+        let source = content.source(note)?;
+        let mut violations = Vec::new();
+
+        for captures in self.regex.captures_iter(&source) {
+            let Some(found) = captures.get(0).filter(|found| !found.is_empty()) else {
+                continue;
+            };
+
+            let mut violation = Violation::new(&self.message, found.range())?;
+
+            if let Some(replacement) = &self.replacement {
+                let mut text = String::new();
+                captures.expand(replacement, &mut text);
+
+                violation = violation.with_fix(Fix::safe([Edit::replace(found.range(), text)]));
+            }
+
+            violations.push(violation);
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_core::rule::content::parse_note;
+
+    fn check(rule: &PatternRule, source: &str) -> Vec<Violation> {
+        let note = parse_note(source, "note.md").unwrap();
+        let content = Content::default().with_source("note.md", source);
+
+        rule.check(&content, &note).unwrap()
+    }
+
+    #[test]
+    fn matches() {
+        let rule = PatternRule::new("no-todo", "TODO:", "Resolve TODO before publishing").unwrap();
+        let violations = check(&rule, "TODO: write\nText\nTODO: check\n");
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].message(), "Resolve TODO before publishing");
+        assert_eq!(violations[1].location(), &(17..22));
+        assert!(violations[0].fix().is_none());
+        assert_eq!(rule.fixable(), None);
+    }
+
+    #[test]
+    fn replacement() {
+        let rule = PatternRule::new("no-http", r"http://(\S+)", "Use HTTPS")
+            .unwrap()
+            .with_replacement("https://$1");
+
+        let violations = check(&rule, "See http://example.com\n");
+        let edit = &violations[0].fix().unwrap().edits()[0];
+
+        assert_eq!(edit.range(), &(4..22));
+        assert_eq!(edit.replacement(), "https://example.com");
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        assert!(PatternRule::new("broken", "(", "Message").is_err());
+    }
+}
@@ -1,5 +1,8 @@
 pub mod mock_vault;
 
+use obsidian_tidy_core::rule::content::parse_note;
+use obsidian_tidy_core::rule::fix::apply_fixes;
+use obsidian_tidy_core::rule::{Content, Rule, Violation};
 use std::sync::LazyLock;
 
 pub use mock_vault::{DefaultNoteGenerator, MockVault, MockVaultBuilder, NoteGenerator};
@@ -9,3 +12,25 @@ pub static DEFAULT_MOCK_VAULT: LazyLock<MockVault> = LazyLock::new(|| {
         .build()
         .unwrap()
 });
+
+/// Check note with `source` by `rule`
+pub fn check_source<R>(rule: &R, source: &str) -> Vec<Violation>
+where
+    R: Rule,
+    R::Error: std::fmt::Debug,
+{
+    let note = parse_note(source, "note.md").unwrap();
+    let content = Content::default().with_source("note.md", source);
+
+    rule.check(&content, &note).unwrap()
+}
+
+/// `source` after fixes of all violations of `rule`
+pub fn fix_source<R>(rule: &R, source: &str) -> String
+where
+    R: Rule,
+    R::Error: std::fmt::Debug,
+{
+    let violations = check_source(rule, source);
+    apply_fixes(source, violations.iter().filter_map(Violation::fix)).0
+}
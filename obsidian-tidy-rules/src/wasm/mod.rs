@@ -0,0 +1,113 @@
+//! Custom rules in WebAssembly plugins
+//!
+//! Plugin is a module without imports which implements host ABI of [`ABI_VERSION`].
+//! Data is passed as UTF-8 JSON in memory of plugin. Output is returned as `i64`
+//! with pointer in high 32 bits and length in low 32 bits.
+//!
+//! Exports:
+//! - `memory`
+//! - `tidy_abi_version() -> i32` returns [`ABI_VERSION`]
+//! - `tidy_alloc(len: i32) -> i32` returns pointer to `len` bytes for input
+//! - `tidy_metadata() -> i64` returns `{ "name", "description", "category", "severity",
+//!   "fixable", "options", "rationale" }`. Only `name` is required
+//! - `tidy_check(ptr: i32, len: i32) -> i64` gets `{ "path", "name", "text", "body",
+//!   "frontmatter", "tags", "links", "options" }` and returns array of
+//!   `{ "message", "from", "to", "edits": [{ "from", "to", "replacement" }], "applicability" }`.
+//!   Positions are byte offsets in `text`, `to` is exclusive
+//!
+//! Every call runs in a new instance with [`FUEL`] and [`MAX_MEMORY`] limits
+
+mod rule;
+
+pub use rule::WasmRule;
+
+use obsidian_tidy_core::rule::SharedErrorRule;
+use obsidian_tidy_core::rule::content::Error as ContentError;
+use obsidian_tidy_core::rule::violation::Error as ViolationError;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+/// Extension of plugins
+pub const EXTENSION: &str = "wasm";
+
+/// Version of host ABI which plugins must implement
+pub const ABI_VERSION: u32 = 1;
+
+/// Fuel of one call of plugin. Plugin which runs out of fuel fails
+pub const FUEL: u64 = 1_000_000_000;
+
+/// Maximum size of memory of plugin in bytes
+pub const MAX_MEMORY: usize = 64 << 20;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("WASM error: {0}")]
+    Wasm(String),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Failed read note: {0}")]
+    Content(#[from] ContentError),
+
+    #[error("Failed create violation: {0}")]
+    Violation(#[from] ViolationError),
+
+    #[error("Plugin implements ABI version {0}, but only version {ABI_VERSION} is supported")]
+    AbiVersion(u32),
+
+    #[error("Plugin doesn't export `memory`")]
+    NoMemory,
+
+    #[error("Note is too large for plugin")]
+    InputTooLarge,
+
+    #[error("Output of plugin is out of its memory")]
+    OutOfBounds,
+}
+
+impl From<wasmtime::Error> for Error {
+    fn from(error: wasmtime::Error) -> Self {
+        Self::Wasm(format!("{error:#}"))
+    }
+}
+
+/// Plugins in `dir` sorted by path. Missing `dir` has no plugins
+pub fn plugins(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut plugins = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == EXTENSION)
+        {
+            plugins.push(path);
+        }
+    }
+
+    plugins.sort();
+    Ok(plugins)
+}
+
+/// Load rules from plugins in `dir`
+#[instrument(err)]
+pub fn load_dir(dir: &Path) -> Result<Vec<SharedErrorRule>, Error> {
+    let plugins = plugins(dir)?;
+    debug!("Found {} WASM rules", plugins.len());
+
+    plugins
+        .iter()
+        .map(|path| Ok(SharedErrorRule::new(WasmRule::new(path)?)))
+        .collect()
+}
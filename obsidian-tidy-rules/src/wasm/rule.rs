@@ -0,0 +1,406 @@
+//! Rule from WebAssembly plugin
+
+use super::{ABI_VERSION, Error, FUEL, MAX_MEMORY};
+use obsidian_parser::note::Note as _;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::links::{self, Link};
+use obsidian_tidy_core::rule::options::{Error as OptionsError, merge};
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Content, Docs, DynRule, Edit, Fix, Options, Rule, Severity, Violation,
+};
+use obsidian_tidy_core::{Note, tags};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, path::Path, sync::Arc};
+use tracing::{instrument, trace};
+use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Metadata of rule from `tidy_metadata`
+#[derive(Debug, Clone, Deserialize)]
+struct Metadata {
+    name: String,
+
+    #[serde(default)]
+    description: String,
+
+    #[serde(default = "other")]
+    category: Category,
+
+    #[serde(default)]
+    severity: Severity,
+
+    #[serde(default)]
+    fixable: Option<Applicability>,
+
+    #[serde(default)]
+    options: Options,
+
+    #[serde(default)]
+    rationale: String,
+}
+
+const fn other() -> Category {
+    Category::Other
+}
+
+/// Input of `tidy_check`
+#[derive(Debug, Serialize)]
+struct Input<'a> {
+    path: Option<String>,
+    name: Option<String>,
+    text: &'a str,
+    body: &'a str,
+    frontmatter: Option<serde_json::Value>,
+    tags: BTreeSet<String>,
+    links: Vec<Link<'a>>,
+    options: &'a Options,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginEdit {
+    from: usize,
+    to: usize,
+    replacement: String,
+}
+
+/// Violation from `tidy_check`
+#[derive(Debug, Deserialize)]
+struct PluginViolation {
+    message: String,
+    from: usize,
+    to: usize,
+
+    #[serde(default)]
+    edits: Vec<PluginEdit>,
+
+    #[serde(default)]
+    applicability: Applicability,
+}
+
+impl TryFrom<PluginViolation> for Violation {
+    type Error = Error;
+
+    fn try_from(violation: PluginViolation) -> Result<Self, Self::Error> {
+        let mut result = Violation::new(violation.message, violation.from..violation.to)?;
+
+        if !violation.edits.is_empty() {
+            let edits = violation
+                .edits
+                .into_iter()
+                .map(|edit| Edit::replace(edit.from..edit.to, edit.replacement));
+
+            result = result.with_fix(Fix::new(violation.applicability, edits));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Data of store of plugin
+struct State {
+    limits: StoreLimits,
+}
+
+/// Compiled plugin
+struct Plugin {
+    engine: Engine,
+    module: Module,
+    metadata: Metadata,
+}
+
+impl Plugin {
+    /// Call `export` in new instance of plugin with `input` and return its output.
+    /// Export without `input` has no arguments
+    fn call(
+        engine: &Engine,
+        module: &Module,
+        export: &str,
+        input: Option<&[u8]>,
+    ) -> Result<Vec<u8>, Error> {
+        let limits = StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build();
+
+        let mut store = Store::new(engine, State { limits });
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(FUEL)?;
+
+        // Plugin has no imports, so it can't access anything outside of its memory
+        let instance = Linker::new(engine).instantiate(&mut store, module)?;
+
+        let version = instance
+            .get_typed_func::<(), u32>(&mut store, "tidy_abi_version")?
+            .call(&mut store, ())?;
+
+        if version != ABI_VERSION {
+            return Err(Error::AbiVersion(version));
+        }
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or(Error::NoMemory)?;
+
+        let output = match input {
+            Some(input) => {
+                let length = u32::try_from(input.len()).map_err(|_| Error::InputTooLarge)?;
+                let pointer = instance
+                    .get_typed_func::<u32, u32>(&mut store, "tidy_alloc")?
+                    .call(&mut store, length)?;
+
+                memory
+                    .write(&mut store, pointer as usize, input)
+                    .map_err(|_| Error::InputTooLarge)?;
+
+                instance
+                    .get_typed_func::<(u32, u32), u64>(&mut store, export)?
+                    .call(&mut store, (pointer, length))?
+            }
+            None => instance
+                .get_typed_func::<(), u64>(&mut store, export)?
+                .call(&mut store, ())?,
+        };
+
+        let pointer = usize::try_from(output >> 32).map_err(|_| Error::OutOfBounds)?;
+        let length =
+            usize::try_from(output & u64::from(u32::MAX)).map_err(|_| Error::OutOfBounds)?;
+
+        memory
+            .data(&store)
+            .get(pointer..pointer.saturating_add(length))
+            .map(<[u8]>::to_vec)
+            .ok_or(Error::OutOfBounds)
+    }
+
+    fn new(bytes: &[u8]) -> Result<Self, Error> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, bytes)?;
+
+        let metadata = Self::call(&engine, &module, "tidy_metadata", None)?;
+        let metadata = serde_json::from_slice(&metadata)?;
+
+        Ok(Self {
+            engine,
+            module,
+            metadata,
+        })
+    }
+
+    fn check(&self, input: &Input) -> Result<Vec<Violation>, Error> {
+        let input = serde_json::to_vec(input)?;
+        let output = Self::call(&self.engine, &self.module, "tidy_check", Some(&input))?;
+
+        serde_json::from_slice::<Vec<PluginViolation>>(&output)?
+            .into_iter()
+            .map(Violation::try_from)
+            .collect()
+    }
+}
+
+/// Rule from WebAssembly plugin. See [module](super) for host ABI
+#[derive(Clone)]
+pub struct WasmRule {
+    plugin: Arc<Plugin>,
+    options: Options,
+}
+
+impl WasmRule {
+    /// Load rule from plugin in `path`
+    #[instrument(err)]
+    pub fn new(path: &Path) -> Result<Self, Error> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Load rule from bytes of module
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let plugin = Plugin::new(bytes)?;
+
+        Ok(Self {
+            options: plugin.metadata.options.clone(),
+            plugin: Arc::new(plugin),
+        })
+    }
+}
+
+impl Rule for WasmRule {
+    type Error = super::Error;
+
+    fn name(&self) -> &str {
+        &self.plugin.metadata.name
+    }
+
+    fn description(&self) -> &str {
+        &self.plugin.metadata.description
+    }
+
+    fn category(&self) -> Category {
+        self.plugin.metadata.category.clone()
+    }
+
+    fn default_severity(&self) -> Severity {
+        self.plugin.metadata.severity
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        self.plugin.metadata.fixable
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(self.plugin.metadata.rationale.clone())
+    }
+
+    fn default_options(&self) -> Options {
+        self.plugin.metadata.options.clone()
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self {
+            plugin: self.plugin.clone(),
+            options: merge(&self.plugin.metadata.options, options)?,
+        })))
+    }
+
+    #[instrument(skip_all, fields(rule = self.name()))]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check of WASM rule");
+
+        let source = content.source(note)?;
+        let text: &str = &source;
+        let path = note.path();
+        let frontmatter = Frontmatter::find(text);
+
+        let input = Input {
+            path: path
+                .as_deref()
+                .map(|path| path.to_string_lossy().into_owned()),
+            name: path
+                .as_deref()
+                .and_then(Path::file_stem)
+                .map(|name| name.to_string_lossy().into_owned()),
+            text,
+            body: frontmatter
+                .as_ref()
+                .map_or(text, |frontmatter| &text[frontmatter.location().end..]),
+            frontmatter: frontmatter
+                .and_then(|frontmatter| frontmatter.parse::<serde_yaml_ng::Value>().ok())
+                .and_then(|frontmatter| serde_json::to_value(frontmatter).ok()),
+            tags: tags::find(text),
+            links: links::find(text),
+            options: &self.options,
+        };
+
+        self.plugin.check(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use obsidian_tidy_core::rule::content::parse_note;
+
+    /// Text module which returns `metadata` and `violations` as is
+    fn plugin(metadata: &str, check: &str) -> String {
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (data (i32.const 512) "{}")
+                (func (export "tidy_abi_version") (result i32) (i32.const 1))
+                (func (export "tidy_alloc") (param i32) (result i32) (i32.const 1024))
+                (func (export "tidy_metadata") (result i64) (i64.const {}))
+                {check}
+            )"#,
+            metadata.replace('"', "\\\""),
+            VIOLATIONS.replace('"', "\\\""),
+            metadata.len(),
+        )
+    }
+
+    const VIOLATIONS: &str = r#"[{"message":"Plugin violation","from":0,"to":4,"edits":[{"from":0,"to":4,"replacement":"Done"}]}]"#;
+
+    fn check_returns_violations() -> String {
+        format!(
+            r#"(func (export "tidy_check") (param i32 i32) (result i64)
+                (i64.or (i64.shl (i64.const 512) (i64.const 32)) (i64.const {})))"#,
+            VIOLATIONS.len()
+        )
+    }
+
+    fn check(rule: &WasmRule, source: &str) -> Result<Vec<Violation>, Error> {
+        let note = parse_note(source, "note.md").unwrap();
+        let content = Content::default().with_source("note.md", source);
+
+        rule.check(&content, &note)
+    }
+
+    #[test]
+    fn violations() {
+        let module = plugin(
+            r#"{"name":"no-todo","category":"content","fixable":"safe","options":{"strict":false}}"#,
+            &check_returns_violations(),
+        );
+
+        let rule = WasmRule::from_bytes(module.as_bytes()).unwrap();
+
+        assert_eq!(rule.name(), "no-todo");
+        assert_eq!(rule.category(), Category::Content);
+        assert_eq!(rule.fixable(), Some(Applicability::Safe));
+        assert!(
+            rule.with_options(&toml::from_str("other = 1").unwrap())
+                .is_err()
+        );
+
+        let violations = check(&rule, "TODO write note").unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message(), "Plugin violation");
+        assert_eq!(violations[0].location(), &(0..4));
+        assert_eq!(
+            violations[0].fix().unwrap().edits()[0].replacement(),
+            "Done"
+        );
+    }
+
+    #[test]
+    fn out_of_fuel() {
+        let module = plugin(
+            r#"{"name":"endless"}"#,
+            r#"(func (export "tidy_check") (param i32 i32) (result i64)
+                (loop $endless (br $endless))
+                (i64.const 0))"#,
+        );
+
+        let rule = WasmRule::from_bytes(module.as_bytes()).unwrap();
+        let Err(Error::Wasm(error)) = check(&rule, "Text") else {
+            unreachable!("plugin must run out of fuel");
+        };
+
+        assert!(error.contains("fuel"));
+    }
+
+    #[test]
+    fn memory_limit() {
+        let module = plugin(r#"{"name":"greedy"}"#, &check_returns_violations()).replace(
+            r#"(memory (export "memory") 1)"#,
+            r#"(memory (export "memory") 2048)"#,
+        );
+
+        assert!(matches!(
+            WasmRule::from_bytes(module.as_bytes()),
+            Err(Error::Wasm(_))
+        ));
+    }
+
+    #[test]
+    fn abi_version() {
+        let module = plugin(r#"{"name":"future"}"#, &check_returns_violations())
+            .replace("(i32.const 1))", "(i32.const 2))");
+
+        assert!(matches!(
+            WasmRule::from_bytes(module.as_bytes()),
+            Err(Error::AbiVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! Rule for search duplicate keys in frontmatter
+
+use super::{Error, entries::entries};
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::{Category, Content, Docs, Rule, Severity, Violation};
+use std::collections::HashSet;
+use tracing::{instrument, trace};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DuplicateKeys;
+
+impl Rule for DuplicateKeys {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "duplicate-keys"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search duplicate keys in frontmatter"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "YAML doesn't allow duplicate keys. Depending on the app, \
+             the note loses all properties or all values of the key except one.",
+        )
+        .bad("---\ntags: [project]\nstatus: active\ntags: [work]\n---\n")
+        .good("---\ntags: [project, work]\nstatus: active\n---\n")
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `DuplicateKeys`");
+
+        let source = content.source(note)?;
+
+        let Some(frontmatter) = Frontmatter::find(&source) else {
+            return Ok(Vec::new());
+        };
+
+        let mut seen = HashSet::new();
+        let mut violations = Vec::new();
+
+        for entry in entries(&frontmatter) {
+            if !seen.insert(entry.key) {
+                violations.push(Violation::new(
+                    format!("Key `{}` is duplicated", entry.key),
+                    entry.key_location,
+                )?);
+            }
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_source;
+
+    #[test]
+    fn duplicates() {
+        let source = "---\ntags: [a]\nstatus: active\ntags: [b]\n\"status\": done\n---\n";
+        let violations = check_source(&DuplicateKeys, source);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].message(), "Key `tags` is duplicated");
+        assert_eq!(&source[violations[1].location().clone()], "\"status\"");
+    }
+
+    #[test]
+    fn unique() {
+        assert!(check_source(&DuplicateKeys, "---\ntags: [a]\nTags: [b]\n---\n").is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Top-level entries of frontmatter found by lines
+//!
+//! Unlike YAML parser, it keeps positions and duplicate keys, so rules can point to keys and fix them
+
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use std::ops::Range;
+
+/// Top-level `key: value` of frontmatter with nested lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// Key without quotes
+    pub key: &'a str,
+
+    /// Byte range of key with quotes in source of note
+    pub key_location: Range<usize>,
+
+    /// Byte range of text after `:` with nested lines in source of note
+    pub value_location: Range<usize>,
+
+    /// Byte range of lines of entry in source of note
+    pub location: Range<usize>,
+}
+
+/// Key of line with its range in line and offset of value. `None` if line isn't `key: value`
+fn key(line: &str) -> Option<(&str, Range<usize>, usize)> {
+    let first = line.chars().next()?;
+
+    let (key, range) = if let quote @ ('"' | '\'') = first {
+        let end = line[1..].find(quote)? + 1;
+        (&line[1..end], 0..end + 1)
+    } else {
+        let end = line
+            .match_indices(':')
+            .map(|(index, _)| index)
+            .find(|&index| {
+                line[index + 1..].starts_with([' ', '\t', '\r', '\n']) || index + 1 == line.len()
+            })?;
+
+        let key = line[..end].trim_end();
+        (key, 0..key.len())
+    };
+
+    let colon = range.end + line[range.end..].find(':')?;
+
+    line[range.end..colon]
+        .trim()
+        .is_empty()
+        .then_some((key, range, colon + 1))
+}
+
+/// Line belongs to the previous entry: nested, item of block list, comment or empty line
+fn is_nested(line: &str) -> bool {
+    line.starts_with([' ', '\t', '-', '#']) || line.trim().is_empty()
+}
+
+/// Top-level entries of `frontmatter`
+#[must_use]
+pub fn entries<'a>(frontmatter: &Frontmatter<'a>) -> Vec<Entry<'a>> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut offset = frontmatter.yaml_start();
+
+    for line in frontmatter.yaml().split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let parsed = if is_nested(line) { None } else { key(line) };
+
+        match (parsed, entries.last_mut()) {
+            (Some((key, range, value)), _) => entries.push(Entry {
+                key,
+                key_location: start + range.start..start + range.end,
+                value_location: start + value..offset,
+                location: start..offset,
+            }),
+            (None, Some(last)) => {
+                last.value_location.end = offset;
+                last.location.end = offset;
+            }
+            (None, None) => {}
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(source: &str) -> Vec<&str> {
+        let frontmatter = Frontmatter::find(source).unwrap();
+
+        entries(&frontmatter)
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect()
+    }
+
+    #[test]
+    fn find() {
+        let source =
+            "---\ntitle: Note\ntags:\n  - a\n- b\n\"due date\": 2024-01-01\nurl: http://a.b\n---\n";
+        let frontmatter = Frontmatter::find(source).unwrap();
+        let entries = entries(&frontmatter);
+
+        assert_eq!(
+            entries.iter().map(|entry| entry.key).collect::<Vec<_>>(),
+            ["title", "tags", "due date", "url"]
+        );
+        assert_eq!(&source[entries[1].location.clone()], "tags:\n  - a\n- b\n");
+        assert_eq!(&source[entries[2].key_location.clone()], "\"due date\"");
+        assert_eq!(&source[entries[3].value_location.clone()], " http://a.b\n");
+    }
+
+    #[test]
+    fn comments_and_invalid_lines() {
+        assert_eq!(
+            keys("---\n# comment\na: 1\n\nb\nc:d\ne:\n---\n"),
+            ["a", "e"]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Rule for search forbidden keys in frontmatter
+
+use super::{Error, entries::entries};
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Content, Docs, DynRule, Edit, Fix, Options, Rule, Violation,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{instrument, trace};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ForbiddenKeysOptions {
+    /// Keys which notes must not have
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForbiddenKeys {
+    options: ForbiddenKeysOptions,
+}
+
+impl ForbiddenKeys {
+    #[must_use]
+    pub const fn new(options: ForbiddenKeysOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Rule for ForbiddenKeys {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "forbidden-keys"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search forbidden keys in frontmatter"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        Some(Applicability::Unsafe)
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Keys left by old templates or other apps, like `permalink` or `uid`, \
+             clutter properties and drift from the keys used in queries. \
+             Fix removes them with their values.",
+        )
+        .bad("---\ntitle: Note\nuid: 20240101\n---\n")
+        .good("---\ntitle: Note\n---\n")
+        .option("keys", "Keys which notes must not have")
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&ForbiddenKeysOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(from_options(options)?))))
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `ForbiddenKeys`");
+
+        if self.options.keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let source = content.source(note)?;
+
+        let Some(frontmatter) = Frontmatter::find(&source) else {
+            return Ok(Vec::new());
+        };
+
+        let mut violations = Vec::new();
+
+        for entry in entries(&frontmatter) {
+            if !self.options.keys.iter().any(|key| key == entry.key) {
+                continue;
+            }
+
+            let violation = Violation::new(
+                format!("Key `{}` is forbidden", entry.key),
+                entry.key_location,
+            )?
+            .with_fix(Fix::unsafe_edits([Edit::delete(entry.location)]));
+
+            violations.push(violation);
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_source, fix_source};
+
+    #[test]
+    fn forbidden() {
+        let options = to_options(&ForbiddenKeysOptions {
+            keys: vec!["uid".to_string(), "aliases".to_string()],
+        });
+        let rule = ForbiddenKeys::default()
+            .with_options(&options)
+            .unwrap()
+            .unwrap();
+
+        let source = "---\ntitle: Note\nuid: 1\naliases:\n  - Old\n---\nText\n";
+        let violations = check_source(&rule, source);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].message(), "Key `uid` is forbidden");
+        assert_eq!(violations[0].location(), &(16..19));
+        assert_eq!(fix_source(&rule, source), "---\ntitle: Note\n---\nText\n");
+    }
+
+    #[test]
+    fn no_keys() {
+        assert!(check_source(&ForbiddenKeys::default(), "---\nuid: 1\n---\n").is_empty());
+    }
+}
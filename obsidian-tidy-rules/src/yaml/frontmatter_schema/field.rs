@@ -0,0 +1,246 @@
+//! Fields of note types
+
+use obsidian_tidy_core::links;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::Value;
+
+/// Type of value of field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FieldType {
+    /// Any value
+    #[default]
+    Any,
+
+    String,
+    Number,
+    Boolean,
+
+    /// `YYYY-MM-DD` with optional time
+    Date,
+
+    /// One of `values`
+    Enum,
+
+    /// List of values
+    List,
+
+    /// Link to note like `"[[Note]]"`
+    Link,
+
+    /// List of links
+    Links,
+}
+
+impl FieldType {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "any value",
+            Self::String => "a string",
+            Self::Number => "a number",
+            Self::Boolean => "a boolean",
+            Self::Date => "a date like `2024-01-31`",
+            Self::Enum => "one of values",
+            Self::List => "a list",
+            Self::Link => "a link like `\"[[Note]]\"`",
+            Self::Links => "a list of links",
+        }
+    }
+}
+
+/// Field of note type in config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldConfig {
+    #[serde(rename = "type", default)]
+    pub kind: FieldType,
+
+    /// Values of `enum`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+
+    /// Regex which every string value must match. Items of lists are matched one by one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+/// Compiled [`FieldConfig`]
+#[derive(Debug, Clone)]
+pub struct Field {
+    kind: FieldType,
+    values: Vec<String>,
+    pattern: Option<Regex>,
+}
+
+/// Scalar as text. `None` for lists, mappings and null
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+/// `YYYY-MM-DD` with optional time after `T` or space
+fn is_date(text: &str) -> bool {
+    let (Some(date), Some(time)) = (text.get(..10), text.get(10..)) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+
+    let is_number = |part: &str, len: usize| {
+        part.len() == len && part.chars().all(|character| character.is_ascii_digit())
+    };
+
+    if !(is_number(year, 4) && is_number(month, 2) && is_number(day, 2)) {
+        return false;
+    }
+
+    let days = match month.parse::<u32>() {
+        Ok(2) => 29,
+        Ok(4 | 6 | 9 | 11) => 30,
+        Ok(1..=12) => 31,
+        _ => return false,
+    };
+
+    let valid_day = day
+        .parse::<u32>()
+        .is_ok_and(|day| (1..=days).contains(&day));
+
+    valid_day && (time.is_empty() || time.starts_with(['T', ' ']))
+}
+
+/// Whole `text` is one wikilink or markdown link
+fn is_link(text: &str) -> bool {
+    let found = links::find(text);
+    matches!(found.as_slice(), [link] if link.location == (0..text.len()))
+}
+
+impl Field {
+    pub fn new(config: &FieldConfig) -> Result<Self, regex::Error> {
+        Ok(Self {
+            kind: config.kind,
+            values: config.values.clone(),
+            pattern: config.pattern.as_deref().map(Regex::new).transpose()?,
+        })
+    }
+
+    /// Problem of `value` of field with `name`. `None` if value is valid
+    pub fn check(&self, name: &str, value: &Value) -> Option<String> {
+        let valid = match self.kind {
+            FieldType::Any => true,
+            FieldType::String => value.is_string(),
+            FieldType::Number => value.is_number(),
+            FieldType::Boolean => value.is_bool(),
+            FieldType::Date => value.as_str().is_some_and(is_date),
+            FieldType::Enum => text(value).is_some_and(|text| self.values.contains(&text)),
+            FieldType::List => value.is_sequence(),
+            FieldType::Link => value.as_str().is_some_and(is_link),
+            FieldType::Links => value
+                .as_sequence()
+                .is_some_and(|items| items.iter().all(|item| item.as_str().is_some_and(is_link))),
+        };
+
+        if !valid {
+            return Some(match self.kind {
+                FieldType::Enum => format!("`{name}` must be one of: {}", self.values.join(", ")),
+                kind => format!("`{name}` must be {}", kind.as_str()),
+            });
+        }
+
+        let pattern = self.pattern.as_ref()?;
+        let items = match value {
+            Value::Sequence(items) => items.iter().filter_map(text).collect(),
+            value => text(value).into_iter().collect::<Vec<_>>(),
+        };
+
+        items
+            .iter()
+            .find(|item| !pattern.is_match(item))
+            .map(|item| format!("`{name}` value `{item}` doesn't match `{pattern}`"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(kind: FieldType) -> Field {
+        Field::new(&FieldConfig {
+            kind,
+            ..FieldConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn yaml(source: &str) -> Value {
+        serde_yaml_ng::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn dates() {
+        assert!(is_date("2024-01-31"));
+        assert!(is_date("2024-01-31T10:00"));
+        assert!(is_date("2024-01-31 10:00"));
+        assert!(!is_date("2024-02-30"));
+        assert!(!is_date("2024-13-01"));
+        assert!(!is_date("31.01.2024"));
+        assert!(!is_date("2024-1-31"));
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(field(FieldType::Number).check("a", &yaml("1")), None);
+        assert_eq!(
+            field(FieldType::Number).check("a", &yaml("one")).unwrap(),
+            "`a` must be a number"
+        );
+        assert_eq!(field(FieldType::Date).check("a", &yaml("2024-01-31")), None);
+        assert_eq!(field(FieldType::Link).check("a", &yaml("'[[Note]]'")), None);
+        assert!(field(FieldType::Link).check("a", &yaml("Note")).is_some());
+        assert_eq!(
+            field(FieldType::Links).check("a", &yaml("['[[A]]', '[[B|b]]']")),
+            None
+        );
+        assert!(
+            field(FieldType::Links)
+                .check("a", &yaml("['[[A]]', B]"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn enum_and_pattern() {
+        let field = Field::new(&FieldConfig {
+            kind: FieldType::Enum,
+            values: vec!["active".to_string(), "done".to_string()],
+            pattern: None,
+        })
+        .unwrap();
+
+        assert_eq!(field.check("status", &yaml("done")), None);
+        assert_eq!(
+            field.check("status", &yaml("todo")).unwrap(),
+            "`status` must be one of: active, done"
+        );
+
+        let field = Field::new(&FieldConfig {
+            kind: FieldType::List,
+            values: Vec::new(),
+            pattern: Some(r"^P-\d+$".to_string()),
+        })
+        .unwrap();
+
+        assert_eq!(field.check("ids", &yaml("[P-1, P-2]")), None);
+        assert_eq!(
+            field.check("ids", &yaml("[P-1, X]")).unwrap(),
+            r"`ids` value `X` doesn't match `^P-\d+$`"
+        );
+    }
+}
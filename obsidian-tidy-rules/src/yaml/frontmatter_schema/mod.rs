@@ -0,0 +1,471 @@
+//! Rule for search frontmatter which doesn't match schema of its note type
+
+pub mod field;
+mod schema;
+
+use super::entries::entries;
+use field::{Field, FieldConfig, FieldType};
+use obsidian_parser::prelude::Note as _;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::content::Error as ContentError;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
+use obsidian_tidy_core::rule::violation::Error as ViolationError;
+use obsidian_tidy_core::rule::{
+    Category, Content, Docs, DynRule, Options, PathFilter, Rule, Violation,
+};
+use obsidian_tidy_core::{Note, tags};
+use schema::Schemas;
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::{Mapping, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, instrument, trace};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed read note: {0}")]
+    Content(#[from] ContentError),
+
+    #[error("Failed create violation: `{0}`")]
+    Violation(#[from] ViolationError),
+
+    #[error("Failed read schema `{path}`: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid JSON in schema `{path}`: {source}")]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid schema `{path}`: {message}")]
+    Schema { path: PathBuf, message: String },
+}
+
+/// Note type in config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct NoteTypeConfig {
+    /// Globs of notes of this type
+    pub folders: Vec<String>,
+
+    /// Tags of notes of this type. Nested tags match too
+    pub tags: Vec<String>,
+
+    /// Keys which note of this type must have
+    pub required: Vec<String>,
+
+    /// Types and patterns of values by keys
+    pub fields: BTreeMap<String, FieldConfig>,
+
+    /// JSON Schema file which frontmatter must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FrontmatterSchemaOptions {
+    /// Note types by names
+    pub types: BTreeMap<String, NoteTypeConfig>,
+}
+
+/// Compiled [`NoteTypeConfig`]
+#[derive(Debug, Clone)]
+struct NoteType {
+    name: String,
+    folders: PathFilter,
+    tags: Vec<String>,
+    required: Vec<String>,
+    fields: Vec<(String, Field)>,
+    schema: Option<PathBuf>,
+}
+
+fn invalid(message: String) -> OptionsError {
+    OptionsError::Invalid(serde::de::Error::custom(message))
+}
+
+/// `path` without first folders. Vault root is unknown to rules, so globs are matched against all of them
+fn suffixes(path: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    let components: Vec<_> = path.components().collect();
+    (0..components.len()).map(move |index| components[index..].iter().collect())
+}
+
+/// Key of frontmatter from JSON pointer like `/status/0`
+fn pointer_key(pointer: &str) -> Option<String> {
+    pointer
+        .split('/')
+        .nth(1)
+        .map(|key| key.replace("~1", "/").replace("~0", "~"))
+}
+
+impl NoteType {
+    fn new(name: &str, config: &NoteTypeConfig) -> Result<Self, OptionsError> {
+        let folders = PathFilter::new(&config.folders, [] as [&str; 0])
+            .map_err(|error| invalid(format!("note type `{name}`: {error}")))?;
+
+        let mut fields = Vec::new();
+
+        for (key, field) in &config.fields {
+            if field.kind == FieldType::Enum && field.values.is_empty() {
+                return Err(invalid(format!(
+                    "field `{key}` of note type `{name}` is enum without values"
+                )));
+            }
+
+            let field = Field::new(field).map_err(|error| {
+                invalid(format!("field `{key}` of note type `{name}`: {error}"))
+            })?;
+
+            fields.push((key.clone(), field));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            folders,
+            tags: config.tags.clone(),
+            required: config.required.clone(),
+            fields,
+            schema: config.schema.clone(),
+        })
+    }
+
+    /// Note is in `folders` or has any of `tags`. Type without both is for all notes
+    fn applies(&self, path: Option<&Path>, tags: &BTreeSet<String>) -> bool {
+        if self.folders.is_empty() && self.tags.is_empty() {
+            return true;
+        }
+
+        let in_folder = !self.folders.is_empty()
+            && path.is_some_and(|path| suffixes(path).any(|path| self.folders.matches(&path)));
+
+        in_folder || tags::contains_any(tags, &self.tags)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FrontmatterSchema {
+    types: Vec<NoteType>,
+    schemas: Schemas,
+}
+
+impl FrontmatterSchema {
+    pub fn new(options: &FrontmatterSchemaOptions) -> Result<Self, OptionsError> {
+        let mut types = Vec::new();
+
+        for (name, config) in &options.types {
+            types.push(NoteType::new(name, config)?);
+        }
+
+        Ok(Self {
+            types,
+            schemas: Schemas::default(),
+        })
+    }
+
+    /// Problems of `mapping` by `note_type` with keys where they are
+    fn problems(
+        &self,
+        note_type: &NoteType,
+        mapping: &Mapping,
+        path: Option<&Path>,
+    ) -> Result<Vec<(Option<String>, String)>, Error> {
+        let mut problems = Vec::new();
+        let name = &note_type.name;
+
+        for key in &note_type.required {
+            if mapping.get(key.as_str()).is_none_or(Value::is_null) {
+                problems.push((
+                    Some(key.clone()),
+                    format!("Note type `{name}` requires key `{key}`"),
+                ));
+            }
+        }
+
+        for (key, field) in &note_type.fields {
+            let Some(value) = mapping.get(key.as_str()).filter(|value| !value.is_null()) else {
+                continue;
+            };
+
+            if let Some(problem) = field.check(key, value) {
+                problems.push((Some(key.clone()), format!("Note type `{name}`: {problem}")));
+            }
+        }
+
+        let Some(schema) = &note_type.schema else {
+            return Ok(problems);
+        };
+
+        let validator = self.schemas.validator(schema, path)?;
+
+        let instance = match serde_json::to_value(mapping) {
+            Ok(instance) => instance,
+            Err(error) => {
+                debug!("Frontmatter isn't JSON: {error}");
+                return Ok(problems);
+            }
+        };
+
+        for error in validator.iter_errors(&instance) {
+            problems.push((
+                pointer_key(&error.instance_path.to_string()),
+                format!("Note type `{name}`: {error}"),
+            ));
+        }
+
+        Ok(problems)
+    }
+}
+
+impl Rule for FrontmatterSchema {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "frontmatter-schema"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search frontmatter which doesn't match schema of its note type"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Notes of one type, like projects or meetings, are queried by the same properties. \
+             Note type selects notes by folders or tags and declares required keys, \
+             types of values and patterns, or a JSON Schema file.",
+        )
+        .bad("---\ntags: [project]\nstatus: someday\ndue: next week\n---\n")
+        .good("---\ntags: [project]\nstatus: active\ndue: 2024-03-01\n---\n")
+        .option(
+            "types",
+            "Note types by names with `folders`, `tags`, `required`, `fields` and `schema`",
+        )
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&FrontmatterSchemaOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(&from_options(options)?)?)))
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `FrontmatterSchema`");
+
+        if self.types.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let source = content.source(note)?;
+        let path = note.path();
+        let path = path.as_deref();
+        let tags = tags::find(&source);
+
+        let types: Vec<&NoteType> = self
+            .types
+            .iter()
+            .filter(|note_type| note_type.applies(path, &tags))
+            .collect();
+
+        if types.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let frontmatter = Frontmatter::find(&source);
+
+        // Invalid frontmatter is reported by `invalid-frontmatter`
+        let mapping = match frontmatter
+            .as_ref()
+            .map(|frontmatter| serde_yaml_ng::from_str::<Value>(frontmatter.yaml()))
+        {
+            None | Some(Ok(Value::Null)) => Mapping::new(),
+            Some(Ok(Value::Mapping(mapping))) => mapping,
+            Some(Ok(_) | Err(_)) => return Ok(Vec::new()),
+        };
+
+        let entries = frontmatter.as_ref().map(entries).unwrap_or_default();
+        let start: Range<usize> = if frontmatter.is_some() { 0..3 } else { 0..0 };
+
+        let mut violations = Vec::new();
+
+        for note_type in types {
+            for (key, message) in self.problems(note_type, &mapping, path)? {
+                let location = entries
+                    .iter()
+                    .find(|entry| key.as_deref() == Some(entry.key))
+                    .map_or_else(|| start.clone(), |entry| entry.key_location.clone());
+
+                violations.push(Violation::new(message, location)?);
+            }
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_source;
+    use obsidian_tidy_core::rule::content::parse_note;
+    use std::io::Write;
+
+    fn rule(types: &str) -> DynRule<Error> {
+        let options: Options = toml::from_str(types).unwrap();
+        FrontmatterSchema::default()
+            .with_options(&options)
+            .unwrap()
+            .unwrap()
+    }
+
+    fn messages(rule: &DynRule<Error>, source: &str) -> Vec<String> {
+        check_source(rule, source)
+            .iter()
+            .map(|violation| violation.message().to_string())
+            .collect()
+    }
+
+    const PROJECT: &str = r#"
+        [types.project]
+        tags = ["project"]
+        required = ["status", "due"]
+        fields.status = { type = "enum", values = ["active", "done"] }
+        fields.due = { type = "date" }
+        fields.people = { type = "links" }
+        fields.code = { pattern = '^P-\d+$' }
+    "#;
+
+    #[test]
+    fn required() {
+        let source = "---\ntags: [project]\nstatus:\n---\n";
+        let violations = check_source(&rule(PROJECT), source);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].message(),
+            "Note type `project` requires key `status`"
+        );
+        assert_eq!(violations[0].location(), &(20..26));
+        assert_eq!(
+            violations[1].message(),
+            "Note type `project` requires key `due`"
+        );
+        assert_eq!(violations[1].location(), &(0..3));
+    }
+
+    #[test]
+    fn fields() {
+        let source = "---\ntags: [project]\nstatus: someday\ndue: next week\n\
+                      people: ['[[Ann]]', Bob]\ncode: X-1\n---\n";
+
+        assert_eq!(
+            messages(&rule(PROJECT), source),
+            [
+                r"Note type `project`: `code` value `X-1` doesn't match `^P-\d+$`",
+                "Note type `project`: `due` must be a date like `2024-01-31`",
+                "Note type `project`: `people` must be a list of links",
+                "Note type `project`: `status` must be one of: active, done",
+            ]
+        );
+    }
+
+    #[test]
+    fn valid() {
+        let source = "---\ntags: [project]\nstatus: active\ndue: 2024-03-01\n\
+                      people: ['[[Ann]]']\ncode: P-1\n---\n";
+
+        assert!(messages(&rule(PROJECT), source).is_empty());
+        assert!(messages(&rule(PROJECT), "---\ntags: [meeting]\n---\n").is_empty());
+    }
+
+    #[test]
+    fn tags_in_text() {
+        assert_eq!(
+            messages(&rule(PROJECT), "Plan #project/big\n"),
+            [
+                "Note type `project` requires key `status`",
+                "Note type `project` requires key `due`",
+            ]
+        );
+    }
+
+    #[test]
+    fn folders() {
+        let rule = rule("[types.meeting]\nfolders = [\"meetings/**\"]\nrequired = [\"date\"]\n");
+        let check = |path: &str| {
+            let note = parse_note("Text", path).unwrap();
+            let content = Content::default().with_source(path, "Text");
+            rule.check(&content, &note).unwrap().len()
+        };
+
+        assert_eq!(check("/vault/meetings/2024/standup.md"), 1);
+        assert_eq!(check("meetings/standup.md"), 1);
+        assert_eq!(check("/vault/projects/plan.md"), 0);
+    }
+
+    #[test]
+    fn invalid_options() {
+        let options: Options =
+            toml::from_str("[types.a]\nfields.status = { type = \"enum\" }\n").unwrap();
+        assert!(FrontmatterSchema::default().with_options(&options).is_err());
+
+        let options: Options =
+            toml::from_str("[types.a]\nfields.b = { pattern = \"(\" }\n").unwrap();
+        assert!(FrontmatterSchema::default().with_options(&options).is_err());
+    }
+
+    #[test]
+    fn json_schema() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{"type": "object", "required": ["title"], "properties": {{"rating": {{"type": "integer", "maximum": 5}}}}}}"#
+        )
+        .unwrap();
+
+        let rule = rule(&format!(
+            "[types.book]\nschema = {:?}\n",
+            file.path().display().to_string()
+        ));
+        let violations = check_source(&rule, "---\nrating: 7\n---\n");
+
+        assert_eq!(violations.len(), 2);
+        assert!(
+            violations
+                .iter()
+                .all(|violation| { violation.message().starts_with("Note type `book`: ") })
+        );
+        assert!(
+            violations
+                .iter()
+                .any(|violation| violation.location() == &(4..10))
+        );
+        assert!(
+            violations
+                .iter()
+                .any(|violation| violation.location() == &(0..3))
+        );
+    }
+
+    #[test]
+    fn pointer() {
+        assert_eq!(pointer_key("/status/0").unwrap(), "status");
+        assert_eq!(pointer_key("/a~1b").unwrap(), "a/b");
+        assert_eq!(pointer_key(""), None);
+    }
+}
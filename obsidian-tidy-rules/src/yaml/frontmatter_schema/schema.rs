@@ -0,0 +1,72 @@
+//! JSON Schema files of note types
+
+use super::Error;
+use jsonschema::Validator;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use tracing::debug;
+
+/// Compiled schemas by their paths. Every file is read once
+#[derive(Clone, Default)]
+pub struct Schemas {
+    validators: Arc<Mutex<HashMap<PathBuf, Arc<Validator>>>>,
+}
+
+impl fmt::Debug for Schemas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Schemas").finish_non_exhaustive()
+    }
+}
+
+/// File of `schema` in the nearest folder of `note`, so paths relative to the vault work.
+/// Otherwise `schema` as is
+fn resolve(schema: &Path, note: Option<&Path>) -> PathBuf {
+    note.into_iter()
+        .flat_map(Path::ancestors)
+        .skip(1)
+        .map(|folder| folder.join(schema))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| schema.to_path_buf())
+}
+
+impl Schemas {
+    /// Validator of `schema` for note with path `note`
+    pub fn validator(&self, schema: &Path, note: Option<&Path>) -> Result<Arc<Validator>, Error> {
+        let path = resolve(schema, note);
+        let mut validators = self
+            .validators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(validator) = validators.get(&path) {
+            return Ok(validator.clone());
+        }
+
+        debug!("Compile schema `{}`", path.display());
+
+        let text = std::fs::read_to_string(&path).map_err(|source| Error::Read {
+            path: path.clone(),
+            source,
+        })?;
+
+        let json = serde_json::from_str(&text).map_err(|source| Error::Json {
+            path: path.clone(),
+            source,
+        })?;
+
+        let validator = jsonschema::options()
+            .should_validate_formats(true)
+            .build(&json)
+            .map_err(|error| Error::Schema {
+                path: path.clone(),
+                message: error.to_string(),
+            })?;
+
+        let validator = Arc::new(validator);
+        validators.insert(path, validator.clone());
+
+        Ok(validator)
+    }
+}
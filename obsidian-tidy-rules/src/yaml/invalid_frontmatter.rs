@@ -0,0 +1,111 @@
+//! Rule for search frontmatter which isn't valid YAML
+
+use super::Error;
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::{Category, Content, Docs, Rule, Severity, Violation};
+use serde_yaml_ng::Value;
+use tracing::{instrument, trace};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvalidFrontmatter;
+
+impl Rule for InvalidFrontmatter {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "invalid-frontmatter"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search frontmatter which isn't valid YAML"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Obsidian and Dataview ignore all properties of note when frontmatter can't be parsed, \
+             so one missing quote hides the whole note from queries.",
+        )
+        .bad("---\ntitle: Note: draft\n---\n")
+        .good("---\ntitle: \"Note: draft\"\n---\n")
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `InvalidFrontmatter`");
+
+        let source = content.source(note)?;
+
+        let Some(frontmatter) = Frontmatter::find(&source) else {
+            return Ok(Vec::new());
+        };
+
+        let yaml_start = frontmatter.yaml_start();
+        let yaml_end = yaml_start + frontmatter.yaml().len();
+
+        let violation = match serde_yaml_ng::from_str::<Value>(frontmatter.yaml()) {
+            Ok(Value::Mapping(_) | Value::Null) => return Ok(Vec::new()),
+            Ok(_) => Violation::new(
+                "Frontmatter must be a mapping of properties",
+                yaml_start..yaml_end,
+            )?,
+            Err(error) => {
+                let start = error
+                    .location()
+                    .map_or(yaml_start, |location| yaml_start + location.index())
+                    .min(yaml_end);
+
+                let end = source[start..yaml_end]
+                    .find('\n')
+                    .map_or(yaml_end, |end| start + end);
+
+                Violation::new(format!("Invalid frontmatter: {error}"), start..end)?
+            }
+        };
+
+        Ok(vec![violation])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_source;
+
+    #[test]
+    fn invalid() {
+        let source = "---\ntitle: Note\nsummary: a: b\n---\n";
+        let violations = check_source(&InvalidFrontmatter, source);
+
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message().starts_with("Invalid frontmatter"));
+        assert!(violations[0].location().start >= 15);
+    }
+
+    #[test]
+    fn not_mapping() {
+        let violations = check_source(&InvalidFrontmatter, "---\n- a\n- b\n---\n");
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message(),
+            "Frontmatter must be a mapping of properties"
+        );
+        assert_eq!(violations[0].location(), &(4..12));
+    }
+
+    #[test]
+    fn valid() {
+        assert!(check_source(&InvalidFrontmatter, "---\ntitle: Note\n---\n").is_empty());
+        assert!(check_source(&InvalidFrontmatter, "---\n---\n").is_empty());
+        assert!(check_source(&InvalidFrontmatter, "No frontmatter").is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Rule for search keys of frontmatter in inconsistent case
+
+use super::{Error, entries::entries};
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
+use obsidian_tidy_core::rule::{Category, Content, Docs, DynRule, Options, Rule, Violation};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{instrument, trace};
+
+/// Case of keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Case {
+    /// All keys of note are in the same case as its first keys
+    #[default]
+    Consistent,
+
+    /// `due_date`
+    Snake,
+
+    /// `due-date`
+    Kebab,
+
+    /// `dueDate`
+    Camel,
+
+    /// `DueDate`
+    Pascal,
+}
+
+impl Case {
+    /// Cases which are checked by [`Case::Consistent`]
+    const KNOWN: [Self; 4] = [Self::Snake, Self::Kebab, Self::Camel, Self::Pascal];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Consistent => "consistent case",
+            Self::Snake => "snake_case",
+            Self::Kebab => "kebab-case",
+            Self::Camel => "camelCase",
+            Self::Pascal => "PascalCase",
+        }
+    }
+
+    /// `key` is in this case. Key of one word in lower case is in any case except Pascal
+    fn matches(self, key: &str) -> bool {
+        let has_upper = key.chars().any(char::is_uppercase);
+        let first_upper = key.chars().next().is_some_and(char::is_uppercase);
+        let is_word = |character: char| character.is_alphanumeric();
+
+        match self {
+            Self::Consistent => true,
+            Self::Snake => !has_upper && key.chars().all(|c| is_word(c) || c == '_'),
+            Self::Kebab => !has_upper && key.chars().all(|c| is_word(c) || c == '-'),
+            Self::Camel => !first_upper && key.chars().all(is_word),
+            Self::Pascal => first_upper && key.chars().all(is_word),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct KeyCaseOptions {
+    /// Case of keys: `consistent`, `snake`, `kebab`, `camel` or `pascal`
+    pub case: Case,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyCase {
+    options: KeyCaseOptions,
+}
+
+impl KeyCase {
+    #[must_use]
+    pub const fn new(options: KeyCaseOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Rule for KeyCase {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "key-case"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search keys of frontmatter in inconsistent case"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Keys are case-sensitive in Dataview queries, so `dueDate` and `due_date` \
+             are different properties. One case of keys across the vault keeps queries working.",
+        )
+        .bad("---\ndue_date: 2024-01-01\ncreatedAt: 2023-12-01\n---\n")
+        .good("---\ndue_date: 2024-01-01\ncreated_at: 2023-12-01\n---\n")
+        .option(
+            "case",
+            "Case of keys: `consistent`, `snake`, `kebab`, `camel` or `pascal`",
+        )
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&KeyCaseOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(from_options(options)?))))
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `KeyCase`");
+
+        let source = content.source(note)?;
+
+        let Some(frontmatter) = Frontmatter::find(&source) else {
+            return Ok(Vec::new());
+        };
+
+        // Cases of keys before. Every key narrows them until only one is left
+        let mut cases = match self.options.case {
+            Case::Consistent => Case::KNOWN.to_vec(),
+            case => vec![case],
+        };
+
+        let mut violations = Vec::new();
+
+        for entry in entries(&frontmatter) {
+            let matching: Vec<Case> = cases
+                .iter()
+                .copied()
+                .filter(|case| case.matches(entry.key))
+                .collect();
+
+            if !matching.is_empty() {
+                cases = matching;
+                continue;
+            }
+
+            let message = match self.options.case {
+                Case::Consistent => format!(
+                    "Key `{}` isn't in {} like previous keys",
+                    entry.key,
+                    cases[0].as_str()
+                ),
+                case => format!("Key `{}` isn't in {}", entry.key, case.as_str()),
+            };
+
+            violations.push(Violation::new(message, entry.key_location)?);
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_source;
+
+    fn messages(rule: &impl Rule<Error = Error>, source: &str) -> Vec<String> {
+        check_source(rule, source)
+            .iter()
+            .map(|violation| violation.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn matches() {
+        assert!(Case::Snake.matches("due_date"));
+        assert!(Case::Snake.matches("title"));
+        assert!(!Case::Snake.matches("due-date"));
+        assert!(Case::Kebab.matches("due-date"));
+        assert!(Case::Camel.matches("dueDate"));
+        assert!(!Case::Camel.matches("DueDate"));
+        assert!(Case::Pascal.matches("DueDate"));
+        assert!(!Case::Pascal.matches("title"));
+    }
+
+    #[test]
+    fn consistent() {
+        let source = "---\ntitle: a\ndue_date: b\ncreatedAt: c\nsome-key: d\n---\n";
+
+        assert_eq!(
+            messages(&KeyCase::default(), source),
+            [
+                "Key `createdAt` isn't in snake_case like previous keys",
+                "Key `some-key` isn't in snake_case like previous keys",
+            ]
+        );
+    }
+
+    #[test]
+    fn explicit() {
+        let rule = KeyCase::new(KeyCaseOptions { case: Case::Camel });
+
+        assert_eq!(
+            messages(&rule, "---\ntitle: a\ndueDate: b\ndue_date: c\n---\n"),
+            ["Key `due_date` isn't in camelCase"]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Rule for search keys of frontmatter out of order
+
+use super::{Error, entries::entries};
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Content, Docs, DynRule, Edit, Fix, Options, Rule, Violation,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{instrument, trace};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct KeyOrderOptions {
+    /// Keys which go first in this order
+    pub order: Vec<String>,
+
+    /// Sort other keys alphabetically. Otherwise they keep their order
+    pub sort: bool,
+}
+
+impl Default for KeyOrderOptions {
+    fn default() -> Self {
+        Self {
+            order: Vec::new(),
+            sort: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyOrder {
+    options: KeyOrderOptions,
+}
+
+impl KeyOrder {
+    #[must_use]
+    pub const fn new(options: KeyOrderOptions) -> Self {
+        Self { options }
+    }
+
+    /// Key which sorts entry with `key`
+    fn rank(&self, key: &str) -> (usize, String) {
+        match self.options.order.iter().position(|ordered| ordered == key) {
+            Some(position) => (position, String::new()),
+            None if self.options.sort => (self.options.order.len(), key.to_lowercase()),
+            None => (self.options.order.len(), String::new()),
+        }
+    }
+}
+
+impl Rule for KeyOrder {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "key-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search keys of frontmatter out of order"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        Some(Applicability::Safe)
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "The same order of keys in every note makes properties easy to scan \
+             and keeps diffs of frontmatter small.",
+        )
+        .bad("---\nstatus: active\ntitle: Plan\n---\n")
+        .good("---\ntitle: Plan\nstatus: active\n---\n")
+        .option("order", "Keys which go first in this order")
+        .option(
+            "sort",
+            "Sort other keys alphabetically. Otherwise they keep their order",
+        )
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&KeyOrderOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(from_options(options)?))))
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `KeyOrder`");
+
+        let source = content.source(note)?;
+
+        let Some(frontmatter) = Frontmatter::find(&source) else {
+            return Ok(Vec::new());
+        };
+
+        let entries = entries(&frontmatter);
+        let mut sorted = entries.clone();
+        sorted.sort_by_cached_key(|entry| self.rank(entry.key));
+
+        let Some((actual, expected)) = entries
+            .iter()
+            .zip(&sorted)
+            .find(|(actual, expected)| actual.key != expected.key)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let location = entries[0].location.start..entries[entries.len() - 1].location.end;
+        let replacement: String = sorted
+            .iter()
+            .map(|entry| &source[entry.location.clone()])
+            .collect();
+
+        let violation = Violation::new(
+            format!("Key `{}` must be before `{}`", expected.key, actual.key),
+            actual.key_location.clone(),
+        )?
+        .with_fix(Fix::safe([Edit::replace(location, replacement)]));
+
+        Ok(vec![violation])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_source, fix_source};
+
+    fn rule(order: &[&str], sort: bool) -> DynRule<Error> {
+        let options = to_options(&KeyOrderOptions {
+            order: order.iter().map(ToString::to_string).collect(),
+            sort,
+        });
+
+        KeyOrder::default().with_options(&options).unwrap().unwrap()
+    }
+
+    #[test]
+    fn sort() {
+        let source = "---\nstatus: active\ntags:\n  - a\nAuthor: Me\n---\nText\n";
+        let violations = check_source(&KeyOrder::default(), source);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message(),
+            "Key `Author` must be before `status`"
+        );
+        assert_eq!(
+            fix_source(&KeyOrder::default(), source),
+            "---\nAuthor: Me\nstatus: active\ntags:\n  - a\n---\nText\n"
+        );
+    }
+
+    #[test]
+    fn order() {
+        let rule = rule(&["title", "tags"], false);
+        let source = "---\nb: 1\ntags: [x]\na: 2\ntitle: Note\n---\n";
+
+        assert_eq!(
+            fix_source(&rule, source),
+            "---\ntitle: Note\ntags: [x]\nb: 1\na: 2\n---\n"
+        );
+    }
+
+    #[test]
+    fn sorted() {
+        let source = "---\na: 1\nb: 2\n---\n";
+
+        assert!(check_source(&KeyOrder::default(), source).is_empty());
+        assert!(check_source(&KeyOrder::default(), "Text").is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+//! Rule for search list properties of frontmatter in other format
+
+use super::{Error, entries::Entry, entries::entries};
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Content, Docs, DynRule, Edit, Fix, Options, Rule, Violation,
+};
+use serde::{Deserialize, Serialize};
+use serde_yaml_ng::{Mapping, Value};
+use std::fmt::Write as _;
+use std::{ops::Range, sync::Arc};
+use tracing::{instrument, trace};
+
+/// Key of tags, they are split by spaces too and written without `#`
+const TAGS: &str = "tags";
+
+/// Style of lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Style {
+    /// Item on every line, like Obsidian writes lists
+    #[default]
+    Block,
+
+    /// `[a, b]`
+    Flow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ListFormatOptions {
+    /// Keys which must be lists
+    pub keys: Vec<String>,
+
+    /// Style of lists: `block` or `flow`
+    pub style: Style,
+}
+
+impl Default for ListFormatOptions {
+    fn default() -> Self {
+        Self {
+            keys: vec![
+                TAGS.to_string(),
+                "aliases".to_string(),
+                "cssclasses".to_string(),
+            ],
+            style: Style::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListFormat {
+    options: ListFormatOptions,
+}
+
+/// Item of list as plain scalar if it keeps its value, otherwise in quotes
+fn scalar(item: &str, style: Style) -> String {
+    let is_plain = serde_yaml_ng::from_str::<Value>(item)
+        .is_ok_and(|value| value.as_str() == Some(item))
+        && !(style == Style::Flow && item.contains([',', '[', ']', '{', '}']));
+
+    if is_plain {
+        item.to_string()
+    } else {
+        serde_json::to_string(item).unwrap_or_else(|_| item.to_string())
+    }
+}
+
+/// Lines of entry without trailing empty lines and comments
+fn trimmed(source: &str, entry: &Entry) -> Range<usize> {
+    let mut end = entry.location.start;
+    let mut offset = entry.location.start;
+
+    for line in source[entry.location.clone()].split_inclusive('\n') {
+        offset += line.len();
+
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with('#') {
+            end = offset;
+        }
+    }
+
+    entry.location.start..end
+}
+
+impl ListFormat {
+    #[must_use]
+    pub const fn new(options: ListFormatOptions) -> Self {
+        Self { options }
+    }
+
+    /// Items of list and problem of its format. `None` if entry can't be fixed
+    fn items(&self, source: &str, entry: &Entry) -> Option<(Vec<String>, Option<String>)> {
+        let mapping: Mapping = serde_yaml_ng::from_str(&source[entry.location.clone()]).ok()?;
+        let is_tags = entry.key == TAGS;
+
+        let (items, mut problem) = match mapping.into_iter().next()?.1 {
+            Value::String(text) => {
+                let separators: &[char] = if is_tags { &[',', ' '] } else { &[','] };
+
+                let items = text
+                    .split(separators)
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(ToString::to_string)
+                    .collect();
+
+                (items, Some(format!("`{}` must be a list", entry.key)))
+            }
+            Value::Sequence(values) if !values.is_empty() => {
+                let items = values
+                    .into_iter()
+                    .map(|value| match value {
+                        Value::String(text) => Some(text),
+                        Value::Number(number) => Some(number.to_string()),
+                        Value::Bool(boolean) => Some(boolean.to_string()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                let is_flow = source[entry.value_location.clone()]
+                    .trim_start()
+                    .starts_with('[');
+
+                let problem = match (self.options.style, is_flow) {
+                    (Style::Block, true) => Some(format!("`{}` must be a block list", entry.key)),
+                    (Style::Flow, false) => Some(format!("`{}` must be a flow list", entry.key)),
+                    _ => None,
+                };
+
+                (items, problem)
+            }
+            _ => return None,
+        };
+
+        if problem.is_none() && is_tags && items.iter().any(|item| item.starts_with('#')) {
+            problem = Some("Tags in frontmatter must not start with `#`".to_string());
+        }
+
+        let items = items
+            .into_iter()
+            .map(|item| {
+                if is_tags {
+                    item.trim_start_matches('#').to_string()
+                } else {
+                    item
+                }
+            })
+            .collect();
+
+        Some((items, problem))
+    }
+
+    /// Entry with key `key` and list of `items`
+    fn render(&self, key: &str, items: &[String]) -> String {
+        let items = items.iter().map(|item| scalar(item, self.options.style));
+
+        match self.options.style {
+            Style::Block => items.fold(format!("{key}:\n"), |mut list, item| {
+                let _ = writeln!(list, "  - {item}");
+                list
+            }),
+            Style::Flow => format!("{key}: [{}]\n", items.collect::<Vec<_>>().join(", ")),
+        }
+    }
+}
+
+impl Rule for ListFormat {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "list-format"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search list properties of frontmatter in other format"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        Some(Applicability::Safe)
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Obsidian reads `tags` and `aliases` written as a string or as a list, \
+             but Dataview sees a string and a list as different types. \
+             Fix splits strings like Obsidian does and rewrites lists in one style.",
+        )
+        .bad("---\ntags: project, work\naliases: [Plan]\n---\n")
+        .good("---\ntags:\n  - project\n  - work\naliases:\n  - Plan\n---\n")
+        .option("keys", "Keys which must be lists")
+        .option("style", "Style of lists: `block` or `flow`")
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&ListFormatOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(from_options(options)?))))
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `ListFormat`");
+
+        let source = content.source(note)?;
+
+        let Some(frontmatter) = Frontmatter::find(&source) else {
+            return Ok(Vec::new());
+        };
+
+        let mut violations = Vec::new();
+
+        for entry in entries(&frontmatter) {
+            if !self.options.keys.iter().any(|key| key == entry.key) {
+                continue;
+            }
+
+            let Some((items, Some(problem))) = self.items(&source, &entry) else {
+                continue;
+            };
+
+            let replacement = self.render(&source[entry.key_location.clone()], &items);
+            let violation =
+                Violation::new(problem, entry.key_location.clone())?.with_fix(Fix::safe([
+                    Edit::replace(trimmed(&source, &entry), replacement),
+                ]));
+
+            violations.push(violation);
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_source, fix_source};
+
+    #[test]
+    fn string() {
+        let source = "---\ntags: project, work\naliases: Plan, Roadmap\ntitle: a, b\n---\n";
+
+        assert_eq!(
+            fix_source(&ListFormat::default(), source),
+            "---\ntags:\n  - project\n  - work\naliases:\n  - Plan\n  - Roadmap\ntitle: a, b\n---\n"
+        );
+    }
+
+    #[test]
+    fn style() {
+        let source = "---\naliases: [Plan, \"a: b\"]\n\n# comment\ntags:\n  - \"#work\"\n---\n";
+        let violations = check_source(&ListFormat::default(), source);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].message(), "`aliases` must be a block list");
+        assert_eq!(
+            violations[1].message(),
+            "Tags in frontmatter must not start with `#`"
+        );
+        assert_eq!(
+            fix_source(&ListFormat::default(), source),
+            "---\naliases:\n  - Plan\n  - \"a: b\"\n\n# comment\ntags:\n  - work\n---\n"
+        );
+    }
+
+    #[test]
+    fn flow() {
+        let rule = ListFormat::new(ListFormatOptions {
+            style: Style::Flow,
+            ..ListFormatOptions::default()
+        });
+
+        assert_eq!(
+            fix_source(&rule, "---\naliases:\n  - Plan, draft\n  - Roadmap\n---\n"),
+            "---\naliases: [\"Plan, draft\", Roadmap]\n---\n"
+        );
+    }
+
+    #[test]
+    fn valid() {
+        let source = "---\ntags:\n  - project\naliases:\ncssclasses: []\n---\n";
+        assert!(check_source(&ListFormat::default(), source).is_empty());
+    }
+}
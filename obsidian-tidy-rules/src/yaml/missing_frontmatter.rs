@@ -0,0 +1,66 @@
+//! Rule for search notes without frontmatter
+
+use super::Error;
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::{Category, Content, Docs, Rule, Violation};
+use tracing::{instrument, trace};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissingFrontmatter;
+
+impl Rule for MissingFrontmatter {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "missing-frontmatter"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search notes without frontmatter"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Properties in frontmatter drive Dataview queries, templates and search. \
+             Note without frontmatter silently drops out of them.",
+        )
+        .bad("# Meeting\n")
+        .good("---\ntags: [meeting]\n---\n# Meeting\n")
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `MissingFrontmatter`");
+
+        let source = content.source(note)?;
+
+        if Frontmatter::find(&source).is_some() {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![Violation::new("Note has no frontmatter", 0..0)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_source;
+
+    #[test]
+    fn missing() {
+        assert_eq!(check_source(&MissingFrontmatter, "# Note\n").len(), 1);
+        assert_eq!(check_source(&MissingFrontmatter, "").len(), 1);
+    }
+
+    #[test]
+    fn exists() {
+        assert!(check_source(&MissingFrontmatter, "---\ntitle: Note\n---\n# Note\n").is_empty());
+        assert!(check_source(&MissingFrontmatter, "---\n---\n").is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+//! Rules for YAML frontmatter
+
+pub mod duplicate_keys;
+pub mod forbidden_keys;
+pub mod frontmatter_schema;
+pub mod invalid_frontmatter;
+pub mod key_case;
+pub mod key_order;
+pub mod list_format;
+pub mod missing_frontmatter;
+pub mod required_keys;
+
+mod entries;
+
+use obsidian_tidy_core::rule::content::Error as ContentError;
+use obsidian_tidy_core::rule::violation::Error as ViolationError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed read note: {0}")]
+    Content(#[from] ContentError),
+
+    #[error("Failed create violation: `{0}`")]
+    Violation(#[from] ViolationError),
+}
@@ -0,0 +1,168 @@
+//! Rule for search notes without required keys in frontmatter
+
+use super::{Error, entries::entries};
+use obsidian_tidy_core::Note;
+use obsidian_tidy_core::frontmatter::Frontmatter;
+use obsidian_tidy_core::rule::options::{Error as OptionsError, from_options, to_options};
+use obsidian_tidy_core::rule::{
+    Applicability, Category, Content, Docs, DynRule, Edit, Fix, Options, Rule, Violation,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tracing::{instrument, trace};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct RequiredKeysOptions {
+    /// Keys which every note must have
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequiredKeys {
+    options: RequiredKeysOptions,
+}
+
+impl RequiredKeys {
+    #[must_use]
+    pub const fn new(options: RequiredKeysOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Rule for RequiredKeys {
+    type Error = Error;
+
+    fn name(&self) -> &'static str {
+        "required-keys"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rule for search notes without required keys in frontmatter"
+    }
+
+    fn category(&self) -> Category {
+        Category::Yaml
+    }
+
+    fn fixable(&self) -> Option<Applicability> {
+        Some(Applicability::Unsafe)
+    }
+
+    fn docs(&self) -> Docs {
+        Docs::new(
+            "Dataview queries filter notes by properties. \
+             Note without a required property doesn't show up in them. \
+             Fix adds missing keys with empty values to fill them later.",
+        )
+        .bad("---\ntags: [project]\n---\n")
+        .good("---\ntags: [project]\nstatus: active\ncreated: 2024-01-01\n---\n")
+        .option("keys", "Keys which every note must have")
+    }
+
+    fn default_options(&self) -> Options {
+        to_options(&RequiredKeysOptions::default())
+    }
+
+    fn with_options(
+        &self,
+        options: &Options,
+    ) -> Result<Option<DynRule<Self::Error>>, OptionsError> {
+        Ok(Some(Arc::new(Self::new(from_options(options)?))))
+    }
+
+    #[instrument(skip_all)]
+    fn check(&self, content: &Content, note: &Note) -> Result<Vec<Violation>, Self::Error> {
+        trace!("Run check `RequiredKeys`");
+
+        if self.options.keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let source = content.source(note)?;
+        let frontmatter = Frontmatter::find(&source);
+        let present = frontmatter.as_ref().map(entries).unwrap_or_default();
+
+        let missing: Vec<&String> = self
+            .options
+            .keys
+            .iter()
+            .filter(|key| !present.iter().any(|entry| entry.key == key.as_str()))
+            .collect();
+
+        let Some(first) = missing.first() else {
+            return Ok(Vec::new());
+        };
+
+        let keys = missing.iter().fold(String::new(), |mut keys, key| {
+            let _ = writeln!(keys, "{key}:");
+            keys
+        });
+
+        let (location, edit) = match &frontmatter {
+            Some(frontmatter) => (
+                0..3,
+                Edit::insert(frontmatter.yaml_start() + frontmatter.yaml().len(), keys),
+            ),
+            None => (0..0, Edit::insert(0, format!("---\n{keys}---\n"))),
+        };
+
+        let mut violations = vec![
+            Violation::new(format!("Missing key `{first}`"), location.clone())?
+                .with_fix(Fix::unsafe_edits([edit])),
+        ];
+
+        for key in &missing[1..] {
+            violations.push(Violation::new(
+                format!("Missing key `{key}`"),
+                location.clone(),
+            )?);
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{check_source, fix_source};
+
+    fn rule(keys: &[&str]) -> DynRule<Error> {
+        let options = to_options(&RequiredKeysOptions {
+            keys: keys.iter().map(ToString::to_string).collect(),
+        });
+
+        RequiredKeys::default()
+            .with_options(&options)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn missing() {
+        let rule = rule(&["status", "created", "tags"]);
+        let source = "---\ntags: [project]\n---\nText\n";
+        let violations = check_source(&rule, source);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].message(), "Missing key `status`");
+        assert_eq!(
+            fix_source(&rule, source),
+            "---\ntags: [project]\nstatus:\ncreated:\n---\nText\n"
+        );
+    }
+
+    #[test]
+    fn without_frontmatter() {
+        let rule = rule(&["status"]);
+
+        assert_eq!(fix_source(&rule, "Text\n"), "---\nstatus:\n---\nText\n");
+    }
+
+    #[test]
+    fn no_keys() {
+        assert!(check_source(&RequiredKeys::default(), "Text\n").is_empty());
+    }
+}
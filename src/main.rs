@@ -1,5 +1,6 @@
-use obsidian_tidy_cli::{Cli, LoggerConfig, Parser};
+use obsidian_tidy_cli::{Cli, ExitStatus, LoggerConfig, Parser};
 use obsidian_tidy_logging::{Logger, LoggerBuilder};
+use std::process::ExitCode;
 
 fn init_logger(logger_config: &LoggerConfig) -> Option<Logger> {
     if logger_config.enable_logger {
@@ -16,14 +17,17 @@ fn init_logger(logger_config: &LoggerConfig) -> Option<Logger> {
     None
 }
 
-fn main() -> color_eyre::Result<()> {
+fn main() -> color_eyre::Result<ExitCode> {
     color_eyre::install()?;
 
     let args = Cli::parse();
     let _logger = init_logger(&args.logger);
 
-    let command = args.command;
-    command.execute(&args)?;
+    let command = args.command.clone();
+    let status = command.execute(&args).unwrap_or_else(|error| {
+        eprintln!("Error: {error}");
+        ExitStatus::from_error(error.as_ref())
+    });
 
-    Ok(())
+    Ok(status.into())
 }